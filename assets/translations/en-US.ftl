@@ -0,0 +1,276 @@
+start = 
+    👋 <b>Hi!</b>
+
+    This bot was made for 🦔 <a href="https://t.me/Mushoku_Tensei_AudioBook">HEDGEHOG.INC</a>.
+    To link your Boosty account, send the /email command with your email address.
+
+    <i>Example:</i> <code>/email test.mail@example.com</code>
+
+    📱 <b>Contacts:</b>
+    Content author's channel: <i><a href="https://t.me/Mushoku_Tensei_AudioBook">Mushoku Tensei</a></i>
+    Bot developer: <i><a href="https://t.me/megaredb">megared</a></i>
+help =
+    The following commands are supported:
+
+    /start — start the bot.
+    /help — show this text.
+    /email — link your Boosty email.
+    /verify — confirm the link with the code from the email.
+    /profile — view your profile.
+    /unlink — unlink your Boosty account.
+    /language — choose the language.
+invalid-email =
+    ❌ The email address is invalid.
+user-found = 
+    ✅ <b>We found a user with the email {$email}.</b>
+
+    Name: <i>{$name}</i>
+    Subscription level: <i>{$level}</i>
+
+    This account is now linked to your Telegram. Use the /profile command to view your details.
+    
+    { $invite-links ->
+        [none] Unable to create an invite link to the group. Try again later with the /profile command.
+       *[other] Personal invite link to the group (requests from other accounts are declined):
+            {$invite-links}
+    }
+user-not-subscribed = 
+    ⚠️ <b>We found a user with the email {$email}, but the account <b>has no</b> active subscription.</b>
+user-already-exists = 
+    ⚠️ <b>This Boosty account is already linked to your Telegram account.</b>
+
+    Use the /profile command to view your subscription.
+no-user-found = 
+    ❌ <b>We couldn't find a user with the email {$email}.</b>
+
+    If this is a mistake, contact the bot developer.
+
+no-profile =
+    ❌ <b>Your Telegram account isn't linked to a Boosty account.</b>
+    
+    Send /start or /help for help.
+profile-api-error = 
+    ❌ <b>An error occurred while looking up your profile on Boosty.</b>
+
+    Possible reasons:
+    - The bot couldn't reach Boosty.
+    - You cancelled your subscription or something happened to your Boosty account.
+
+    If you didn't cancel your subscription or change your account, contact the bot developer.
+profile = 
+    👤 <b>Profile</b>
+    
+    🏷 Name: <i>{$name}</i>
+    📬 Email: <i>{$email}</i> 
+    🤩 Subscription level: <i>{$level}</i>
+    💸 Price: <i>{$price} RUB</i>
+    🗓 Valid until: <i>{$expires-at}</i>{ $approximate ->
+        [yes] {""}
+
+            ⚠️ <i>Warning! The "Valid until" date is approximate and may differ from the actual one.</i>
+       *[other] {""}
+    }
+
+    { $invite-links ->
+        [none] Unable to create an invite link to the group. Try again later with the /profile command.
+       *[other] Personal invite link to the group (requests from other accounts are declined):
+            {$invite-links}
+    }
+verification-code-sent =
+    📬 <b>We sent a confirmation code to {$email}.</b>
+
+    To finish linking, send the code with the /verify command.
+    <i>Example:</i> <code>/verify 123456</code>
+
+    The code is valid for {$minutes} min.
+verification-email-error =
+    ❌ <b>Unable to send an email to {$email}.</b>
+
+    Please try again later.
+verification-email-subject = HEDGEHOG.INC confirmation code
+verification-email-body =
+    Your code for linking your Boosty account to Telegram: {$code}
+
+    The code is valid for {$minutes} min. If you didn't request linking, just ignore this email.
+no-pending-verification =
+    ❌ <b>There's no link waiting for confirmation.</b>
+
+    Send the /email command with your email address first.
+verification-expired =
+    ⌛️ <b>The code has expired or you ran out of attempts.</b>
+
+    Request a new code with the /email command.
+verification-invalid-code =
+    { $attempts-left ->
+        [0] ❌ <b>Invalid code.</b> No attempts left, request a new code with the /email command.
+       *[other] ❌ <b>Invalid code.</b> Attempts left: {$attempts-left}.
+    }
+verification-cooldown =
+    ⏳ <b>A confirmation code was sent recently.</b>
+
+    You can request a new code after <i>{$available-at}</i>.
+boosty-api-error =
+    ❌ <b>Unable to reach Boosty.</b>
+
+    Please try again later.
+expiry-reminder =
+    ⏳ <b>Your subscription ends soon.</b>
+
+    { $days-left ->
+        [one] {$days-left} day left
+       *[other] {$days-left} days left
+    } — access is valid until <i>{$expires-at}</i>.
+
+    Renew your Boosty subscription to keep access to the group.
+access-revoked =
+    🚫 <b>Your access to the group has been closed.</b>
+
+    Your Boosty subscription is no longer active, so the account link was removed.
+    Subscribe again and link your email with the /email command to regain access.
+access-grace =
+    ⚠️ <b>We couldn't confirm your Boosty subscription payment.</b>
+
+    You keep access to the group until <i>{$suspend-at}</i>. If the renewal failed because of your card, update the payment method on Boosty — access will continue without linking again.
+access-suspended =
+    ⏸ <b>Your access to the group is suspended.</b>
+
+    Your Boosty subscription is still inactive. The account link is kept until <i>{$remove-at}</i>: as soon as the subscription resumes, the bot will send you a new invite link.
+access-restored =
+    ✅ <b>Your Boosty subscription is active again, access restored.</b>
+
+    { $invite-links ->
+        [none] Unable to create an invite link to the group. Try again later with the /profile command.
+       *[other] Personal invite link to the group (requests from other accounts are declined):
+            {$invite-links}
+    }
+unlink-confirm =
+    ⚠️ <b>Unlink the Boosty account from your Telegram account?</b>
+
+    Your personal invite links will stop working and you will be removed from the groups.
+unlink-button-confirm = Unlink
+unlink-button-cancel = Cancel
+unlink-done = ✅ <b>The Boosty account is unlinked.</b> You can link it again with the /email command.
+unlink-cancelled = 👌 <b>The Boosty account stays linked.</b>
+relink-code-sent =
+    ⚠️ <b>This Boosty account is linked to another Telegram account.</b>
+
+    We sent a confirmation code to the Boosty account's email. Once you send the code with the /verify command, the account will move here and the previous Telegram account will lose access to the groups.
+relink-cooldown =
+    ⏳ <b>This Boosty account was recently linked to another Telegram account.</b>
+
+    It can be moved after <i>{$available-at}</i>. If you think this is a mistake, contact the bot developer.
+account-moved =
+    ⚠️ <b>Your Boosty account was linked to another Telegram account.</b>
+
+    This Telegram account no longer has access to the groups. If it wasn't you, contact the bot developer.
+account-blocked =
+    ⛔️ <b>This Boosty account is blocked: too many Telegram accounts have used it.</b>
+
+    If you think this is a mistake, contact the bot developer.
+join-declined =
+    { $reason ->
+        [not-linked]
+            ❌ <b>Your join request was declined: your Telegram account isn't linked to a Boosty account.</b>
+
+            Link your Boosty email with the /email command and send the request again.
+        [unpaid]
+            ❌ <b>Your join request was declined: you have no active Boosty subscription.</b>
+
+            Subscribe or renew your subscription and send the request again.
+        [not-found]
+            ❌ <b>Your join request was declined: your subscription wasn't found on Boosty.</b>
+
+            Subscribe and send the request again.
+        [level-mismatch]
+            ❌ <b>Your join request was declined: your subscription level doesn't include this group.</b>
+
+            Upgrade your Boosty subscription level and send the request again.
+        [api-error]
+            ⏳ <b>We couldn't check your subscription because of a temporary Boosty error.</b>
+
+            Please send the request again in a few minutes.
+        [shared]
+            ⛔️ <b>Your join request was declined: the Boosty account is blocked for sharing.</b>
+
+            If you think this is a mistake, contact the bot developer.
+        [foreign-link]
+            ❌ <b>Your join request was declined: this invite link was issued to another user.</b>
+
+            Get your own personal link with the /profile command.
+       *[other]
+            ❌ <b>Your join request was declined.</b>
+
+            If you think this is a mistake, contact the bot developer.
+    }
+join-declined-button-email = Link Boosty email
+join-declined-button-subscribe = Open Boosty
+language-name = 🇬🇧 English
+language-select = 🌐 <b>Choose the bot language:</b>
+language-changed = ✅ <b>The bot language is now English.</b>
+admin-help =
+    Admin commands:
+
+    /admin — show this text.
+    /lookup — find a link by Telegram ID, Boosty ID or email.
+    /link — link a Telegram ID to a Boosty ID.
+    /unlink — unlink a Telegram ID.
+    /unblock — clear the account sharing flag of a Boosty ID in a blog.
+    /events — show the latest access decisions for a Telegram ID.
+    /recheck — recheck a user's subscription.
+    /sync — run the subscriber check.
+    /stats — show statistics.
+    /reload — reload the translations.
+admin-forbidden = ⛔️ <b>This command is only available to admins.</b>
+admin-not-found = 🔍 <b>Nothing found.</b>
+admin-user =
+    👤 Telegram ID: <code>{$tg-id}</code>
+    Boosty ID: <code>{$boosty-id}</code>
+    Blog: <i>{$blog}</i>
+    Valid until: <i>{$expires-at}</i>
+    State: { $state ->
+        [grace] <i>grace period</i>
+        [suspended] <i>suspended</i>
+       *[active] <i>active</i>
+    }
+admin-linked = ✅ <b>Account linked:</b> <i>{$name}</i> ({$level}).
+admin-link-taken = ❌ <b>This Boosty account is already linked to Telegram ID <code>{$tg-id}</code>.</b>
+admin-unlinked = ✅ <b>Telegram ID <code>{$tg-id}</code> is unlinked.</b>
+admin-unblocked = ✅ <b>Flag removed from Boosty ID <code>{$boosty-id}</code> (<i>{$blog}</i>).</b>
+sharing-detected =
+    { $verdict ->
+        [block] ⛔️ <b>Boosty account blocked for sharing.</b>
+       *[other] ⚠️ <b>Suspected Boosty account sharing.</b>
+    }
+
+    Boosty ID: <code>{$boosty-id}</code> (<i>{$blog}</i>)
+    Telegram accounts: {$users}
+    Telegram IDs: <code>{$tg-ids}</code>
+
+    Remove the flag: /unblock {$blog} {$boosty-id}
+admin-event =
+    <code>{$created-at}</code> { $decision ->
+        [approved] ✅ approved
+        [declined] ❌ declined
+        [deferred] ⏳ deferred
+        [kicked] 🚪 kicked
+        [suspended] ⏸ suspended
+        [removed] 🗑 removed
+        [restored] ▶️ restored
+       *[other] {$decision}
+    } ({$reason}), chat: { $chat-id ->
+        [none] all
+       *[other] <code>{$chat-id}</code>
+    }, source: <i>{$actor}</i>
+admin-report = 📊 <b>Check result:</b> {$report}
+admin-reloaded = ✅ <b>Translations reloaded:</b> {$locales}.
+admin-reload-failed = ❌ <b>Unable to reload translations, keeping the current ones:</b> {$error}
+admin-stats =
+    📊 <b>Statistics</b>
+
+    Linked accounts: {$total}
+    In grace period: {$in-grace}
+    Suspended: {$suspended}
+    Awaiting email confirmation: {$pending}
+    Deferred join requests: {$deferred}
+
+    Boosty API: <i>{$rate-limiter}</i>
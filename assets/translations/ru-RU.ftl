@@ -9,6 +9,16 @@ start =
     📱 <b>Контакты:</b>
     Канал автора контента: <i><a href="https://t.me/Mushoku_Tensei_AudioBook">Реинкарнация Безработного</a></i>
     Разработчик бота: <i><a href="https://t.me/megaredb">megared</a></i>
+help =
+    Поддерживаются следующие команды:
+
+    /start — запустить бота.
+    /help — отобразить этот текст.
+    /email — привязать почту Boosty.
+    /verify — подтвердить привязку кодом из письма.
+    /profile — просмотреть свой профиль.
+    /unlink — отвязать аккаунт Boosty.
+    /language — выбрать язык.
 invalid-email =
     ❌ Почта указана неверно.
 user-found = 
@@ -19,13 +29,17 @@ user-found =
 
     Теперь этот аккаунт привязан к вашему Telegram. Используйте команду /profile для просмотра данных.
     
-    Ссылка для вступления в группу: https://t.me/+nj3Egg0X1ZxiN2Ji
+    { $invite-links ->
+        [none] Не удалось создать ссылку для вступления в группу. Попробуйте позже с помощью команды /profile.
+       *[other] Персональная ссылка для вступления в группу (заявки с других аккаунтов будут отклонены):
+            {$invite-links}
+    }
 user-not-subscribed = 
     ⚠️ <b>Мы нашли пользователя с почтой {$email}, но его аккаунт <b>не имеет</b> активной подписки.</b>
 user-already-exists = 
-    ⚠️ <b>Данный аккаунт уже существует и привязан к аккаунту Telegram.</b>
+    ⚠️ <b>Данный аккаунт Boosty уже привязан к Вашему аккаунту Telegram.</b>
 
-    Если считаете, что это ошибка, обратитесь к разработчику бота.
+    Просмотреть подписку можно с помощью команды /profile.
 no-user-found = 
     ❌ <b>Мы не нашли пользователя с почтой {$email}.</b>
 
@@ -50,8 +64,214 @@ profile =
     📬 Почта: <i>{$email}</i> 
     🤩 Уровень подписки: <i>{$level}</i>
     💸 Стоимость: <i>{$price} RUB</i>
-    🗓 Действует до: <i>{$expires-at}</i>
+    🗓 Действует до: <i>{$expires-at}</i>{ $approximate ->
+        [yes] {""}
+
+            ⚠️ <i>Внимание! Значение поля "Действует до" является приблизительным значением, которое может отличаться от действительного.</i>
+       *[other] {""}
+    }
+
+    { $invite-links ->
+        [none] Не удалось создать ссылку для вступления в группу. Попробуйте позже с помощью команды /profile.
+       *[other] Персональная ссылка для вступления в группу (заявки с других аккаунтов будут отклонены):
+            {$invite-links}
+    }
+verification-code-sent =
+    📬 <b>Мы отправили код подтверждения на почту {$email}.</b>
+
+    Чтобы завершить привязку, введите код командой /verify.
+    <i>Пример:</i> <code>/verify 123456</code>
+
+    Код действует {$minutes} мин.
+verification-email-error =
+    ❌ <b>Не удалось отправить письмо на почту {$email}.</b>
+
+    Попробуйте ещё раз позже.
+verification-email-subject = Код подтверждения для HEDGEHOG.INC
+verification-email-body =
+    Ваш код для привязки аккаунта Boosty к Telegram: {$code}
+
+    Код действует {$minutes} мин. Если вы не запрашивали привязку, просто проигнорируйте это письмо.
+no-pending-verification =
+    ❌ <b>Нет привязки, ожидающей подтверждения.</b>
+
+    Сначала введите команду /email с вашей почтой.
+verification-expired =
+    ⌛️ <b>Срок действия кода истёк или превышено число попыток.</b>
+
+    Запросите новый код командой /email.
+verification-invalid-code =
+    { $attempts-left ->
+        [0] ❌ <b>Неверный код.</b> Попытки закончились, запросите новый код командой /email.
+       *[other] ❌ <b>Неверный код.</b> Осталось попыток: {$attempts-left}.
+    }
+verification-cooldown =
+    ⏳ <b>Код подтверждения уже был отправлен недавно.</b>
+
+    Запросить новый код можно после <i>{$available-at}</i>.
+boosty-api-error =
+    ❌ <b>Не удалось связаться с Boosty.</b>
+
+    Попробуйте ещё раз позже.
+expiry-reminder =
+    ⏳ <b>Ваша подписка скоро закончится.</b>
+
+    { $days-left ->
+        [one] Остался {$days-left} день
+        [few] Осталось {$days-left} дня
+       *[many] Осталось {$days-left} дней
+    } — доступ действует до <i>{$expires-at}</i>.
+
+    Продлите подписку на Boosty, чтобы не потерять доступ к группе.
+access-revoked =
+    🚫 <b>Ваш доступ к группе закрыт.</b>
+
+    Подписка на Boosty больше не активна, поэтому привязка аккаунта была удалена.
+    Оформите подписку заново и привяжите почту командой /email, чтобы вернуть доступ.
+access-grace =
+    ⚠️ <b>Не удалось подтвердить оплату подписки на Boosty.</b>
+
+    Доступ к группе сохранится до <i>{$suspend-at}</i>. Если продление не прошло из-за карты, обновите способ оплаты на Boosty — доступ продолжит работать без повторной привязки.
+access-suspended =
+    ⏸ <b>Доступ к группе приостановлен.</b>
+
+    Подписка на Boosty всё ещё не активна. Привязка аккаунта сохранится до <i>{$remove-at}</i>: как только подписка возобновится, бот пришлёт новую ссылку для вступления.
+access-restored =
+    ✅ <b>Подписка на Boosty снова активна, доступ восстановлен.</b>
+
+    { $invite-links ->
+        [none] Не удалось создать ссылку для вступления в группу. Попробуйте позже с помощью команды /profile.
+       *[other] Персональная ссылка для вступления в группу (заявки с других аккаунтов будут отклонены):
+            {$invite-links}
+    }
+unlink-confirm =
+    ⚠️ <b>Отвязать аккаунт Boosty от Вашего аккаунта Telegram?</b>
+
+    Персональные ссылки для вступления в группы перестанут действовать, а Вы будете удалены из групп.
+unlink-button-confirm = Отвязать
+unlink-button-cancel = Отмена
+unlink-done = ✅ <b>Аккаунт Boosty отвязан.</b> Привязать его снова можно с помощью команды /email.
+unlink-cancelled = 👌 <b>Аккаунт Boosty остаётся привязанным.</b>
+relink-code-sent =
+    ⚠️ <b>Этот аккаунт Boosty привязан к другому аккаунту Telegram.</b>
+
+    Мы отправили код подтверждения на почту аккаунта Boosty. После ввода кода командой /verify аккаунт будет перенесён сюда, а прежний аккаунт Telegram потеряет доступ к группам.
+relink-cooldown =
+    ⏳ <b>Этот аккаунт Boosty недавно был привязан к другому аккаунту Telegram.</b>
+
+    Перенести его можно будет после <i>{$available-at}</i>. Если считаете, что это ошибка, обратитесь к разработчику бота.
+account-moved =
+    ⚠️ <b>Ваш аккаунт Boosty был привязан к другому аккаунту Telegram.</b>
+
+    Доступ к группам для этого аккаунта Telegram закрыт. Если это были не Вы, обратитесь к разработчику бота.
+account-blocked =
+    ⛔️ <b>Этот аккаунт Boosty заблокирован: им пользовались слишком многие аккаунты Telegram.</b>
+
+    Если считаете, что это ошибка, обратитесь к разработчику бота.
+join-declined =
+    { $reason ->
+        [not-linked]
+            ❌ <b>Заявка на вступление отклонена: Ваш аккаунт Telegram не привязан к аккаунту Boosty.</b>
+
+            Привяжите почту Boosty с помощью команды /email и отправьте заявку снова.
+        [unpaid]
+            ❌ <b>Заявка на вступление отклонена: у Вас нет активной подписки на Boosty.</b>
+
+            Оформите или продлите подписку и отправьте заявку снова.
+        [not-found]
+            ❌ <b>Заявка на вступление отклонена: Ваша подписка не найдена на Boosty.</b>
+
+            Оформите подписку и отправьте заявку снова.
+        [level-mismatch]
+            ❌ <b>Заявка на вступление отклонена: Ваш уровень подписки не включает эту группу.</b>
+
+            Повысьте уровень подписки на Boosty и отправьте заявку снова.
+        [api-error]
+            ⏳ <b>Не удалось проверить подписку из-за временной ошибки Boosty.</b>
+
+            Пожалуйста, отправьте заявку снова через несколько минут.
+        [shared]
+            ⛔️ <b>Заявка на вступление отклонена: аккаунт Boosty заблокирован за передачу.</b>
+
+            Если считаете, что это ошибка, обратитесь к разработчику бота.
+        [foreign-link]
+            ❌ <b>Заявка на вступление отклонена: эта ссылка выдана другому пользователю.</b>
+
+            Получите свою персональную ссылку с помощью команды /profile.
+       *[other]
+            ❌ <b>Заявка на вступление отклонена.</b>
+
+            Если считаете, что это ошибка, обратитесь к разработчику бота.
+    }
+join-declined-button-email = Привязать почту Boosty
+join-declined-button-subscribe = Открыть Boosty
+language-name = 🇷🇺 Русский
+language-select = 🌐 <b>Выберите язык бота:</b>
+language-changed = ✅ <b>Язык бота изменён на русский.</b>
+admin-help =
+    Команды администратора:
+
+    /admin — отобразить этот текст.
+    /lookup — найти привязку по Telegram ID, Boosty ID или почте.
+    /link — привязать Telegram ID к Boosty ID.
+    /unlink — отвязать Telegram ID.
+    /unblock — снять отметку о передаче аккаунта с Boosty ID в блоге.
+    /events — показать последние решения о доступе для Telegram ID.
+    /recheck — перепроверить подписку пользователя.
+    /sync — запустить проверку подписчиков.
+    /stats — показать статистику.
+    /reload — перезагрузить переводы.
+admin-forbidden = ⛔️ <b>Эта команда доступна только администраторам.</b>
+admin-not-found = 🔍 <b>Ничего не найдено.</b>
+admin-user =
+    👤 Telegram ID: <code>{$tg-id}</code>
+    Boosty ID: <code>{$boosty-id}</code>
+    Блог: <i>{$blog}</i>
+    Действует до: <i>{$expires-at}</i>
+    Статус: { $state ->
+        [grace] <i>льготный период</i>
+        [suspended] <i>приостановлен</i>
+       *[active] <i>активен</i>
+    }
+admin-linked = ✅ <b>Аккаунт привязан:</b> <i>{$name}</i> ({$level}).
+admin-link-taken = ❌ <b>Этот аккаунт Boosty уже привязан к Telegram ID <code>{$tg-id}</code>.</b>
+admin-unlinked = ✅ <b>Привязка Telegram ID <code>{$tg-id}</code> удалена.</b>
+admin-unblocked = ✅ <b>Отметка с Boosty ID <code>{$boosty-id}</code> (<i>{$blog}</i>) снята.</b>
+sharing-detected =
+    { $verdict ->
+        [block] ⛔️ <b>Аккаунт Boosty заблокирован за передачу.</b>
+       *[other] ⚠️ <b>Подозрение на передачу аккаунта Boosty.</b>
+    }
+
+    Boosty ID: <code>{$boosty-id}</code> (<i>{$blog}</i>)
+    Аккаунтов Telegram: {$users}
+    Telegram ID: <code>{$tg-ids}</code>
+
+    Снять отметку: /unblock {$blog} {$boosty-id}
+admin-event =
+    <code>{$created-at}</code> { $decision ->
+        [approved] ✅ одобрено
+        [declined] ❌ отклонено
+        [deferred] ⏳ отложено
+        [kicked] 🚪 исключён
+        [suspended] ⏸ приостановлен
+        [removed] 🗑 удалён
+        [restored] ▶️ восстановлен
+       *[other] {$decision}
+    } ({$reason}), чат: { $chat-id ->
+        [none] все
+       *[other] <code>{$chat-id}</code>
+    }, источник: <i>{$actor}</i>
+admin-report = 📊 <b>Результат проверки:</b> {$report}
+admin-reloaded = ✅ <b>Переводы перезагружены:</b> {$locales}.
+admin-reload-failed = ❌ <b>Не удалось перезагрузить переводы, остались прежние:</b> {$error}
+admin-stats =
+    📊 <b>Статистика</b>
 
-    ⚠️ <i>Внимание! Значение поля "Действует до" является приблизительным значением, которое может отличаться от действительного.</i>
+    Привязанных аккаунтов: {$total}
+    В льготном периоде: {$in-grace}
+    Приостановлено: {$suspended}
+    Ожидают подтверждения почты: {$pending}
+    Отложенных заявок на вступление: {$deferred}
 
-    Ссылка для вступления в группу: https://t.me/+nj3Egg0X1ZxiN2Ji
\ No newline at end of file
+    Boosty API: <i>{$rate-limiter}</i>
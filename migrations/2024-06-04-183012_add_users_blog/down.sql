@@ -0,0 +1,4 @@
+-- This file should undo anything in `up.sql`
+ALTER TABLE "users" DROP CONSTRAINT IF EXISTS "users_pkey";
+ALTER TABLE "users" ADD PRIMARY KEY ("id");
+ALTER TABLE "users" DROP COLUMN IF EXISTS "blog";
@@ -0,0 +1,5 @@
+-- Your SQL goes here
+ALTER TABLE "users" ADD COLUMN "blog" VARCHAR NOT NULL DEFAULT 'hedgehoginc';
+ALTER TABLE "users" ALTER COLUMN "blog" DROP DEFAULT;
+ALTER TABLE "users" DROP CONSTRAINT "users_pkey";
+ALTER TABLE "users" ADD PRIMARY KEY ("id", "blog");
@@ -0,0 +1,9 @@
+-- Your SQL goes here
+CREATE TABLE "boosty_credentials" (
+	"name" VARCHAR NOT NULL PRIMARY KEY,
+	"access_token" VARCHAR NOT NULL,
+	"refresh_token" VARCHAR NOT NULL,
+	"device_id" VARCHAR NOT NULL,
+	"expires_at" BIGINT NOT NULL,
+	"updated_at" TIMESTAMP NOT NULL DEFAULT NOW()
+);
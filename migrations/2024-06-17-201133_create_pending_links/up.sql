@@ -0,0 +1,12 @@
+-- Your SQL goes here
+CREATE TABLE "pending_links" (
+	"id" BIGINT NOT NULL PRIMARY KEY,
+	"boosty_id" BIGINT NOT NULL,
+	"blog" VARCHAR NOT NULL,
+	"email" VARCHAR NOT NULL,
+	"code" VARCHAR NOT NULL,
+	"attempts" INTEGER NOT NULL DEFAULT 0,
+	"expires_at" TIMESTAMP NOT NULL,
+	"sent_at" TIMESTAMP NOT NULL
+);
+CREATE INDEX "pending_links_email_idx" ON "pending_links" ("email");
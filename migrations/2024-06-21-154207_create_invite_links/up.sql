@@ -0,0 +1,8 @@
+-- Your SQL goes here
+CREATE TABLE "invite_links" (
+	"user_id" BIGINT NOT NULL,
+	"chat_id" BIGINT NOT NULL,
+	"invite_link" VARCHAR NOT NULL,
+	"expires_at" TIMESTAMP NOT NULL,
+	PRIMARY KEY ("user_id", "chat_id")
+);
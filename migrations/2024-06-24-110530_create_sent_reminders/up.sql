@@ -0,0 +1,8 @@
+-- Your SQL goes here
+CREATE TABLE "sent_reminders" (
+	"user_id" BIGINT NOT NULL,
+	"expires_at" TIMESTAMP NOT NULL,
+	"days" INTEGER NOT NULL,
+	"sent_at" TIMESTAMP NOT NULL DEFAULT NOW(),
+	PRIMARY KEY ("user_id", "expires_at", "days")
+);
@@ -0,0 +1,9 @@
+-- Your SQL goes here
+CREATE TABLE "expiry_history" (
+	"id" BIGSERIAL NOT NULL PRIMARY KEY,
+	"user_id" BIGINT NOT NULL,
+	"old_expires_at" TIMESTAMP,
+	"new_expires_at" TIMESTAMP NOT NULL,
+	"changed_at" TIMESTAMP NOT NULL DEFAULT NOW()
+);
+CREATE INDEX "expiry_history_user_id_idx" ON "expiry_history" ("user_id");
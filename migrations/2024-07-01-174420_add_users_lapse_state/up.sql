@@ -0,0 +1,3 @@
+-- Your SQL goes here
+ALTER TABLE "users" ADD COLUMN "lapsed_at" TIMESTAMP;
+ALTER TABLE "users" ADD COLUMN "suspended" BOOLEAN NOT NULL DEFAULT FALSE;
@@ -0,0 +1,8 @@
+-- Your SQL goes here
+CREATE TABLE "link_changes" (
+	"blog" VARCHAR NOT NULL,
+	"boosty_id" BIGINT NOT NULL,
+	"user_id" BIGINT NOT NULL,
+	"changed_at" TIMESTAMP NOT NULL DEFAULT NOW(),
+	PRIMARY KEY ("blog", "boosty_id")
+);
@@ -0,0 +1,3 @@
+-- This file should undo anything in `up.sql`
+DROP TABLE IF EXISTS "account_flags";
+DROP TABLE IF EXISTS "link_events";
@@ -0,0 +1,18 @@
+-- Your SQL goes here
+CREATE TABLE "link_events" (
+	"id" BIGSERIAL NOT NULL PRIMARY KEY,
+	"blog" VARCHAR NOT NULL,
+	"boosty_id" BIGINT NOT NULL,
+	"user_id" BIGINT NOT NULL,
+	"kind" VARCHAR NOT NULL,
+	"created_at" TIMESTAMP NOT NULL DEFAULT NOW()
+);
+CREATE INDEX "link_events_account_idx" ON "link_events" ("blog", "boosty_id", "created_at");
+
+CREATE TABLE "account_flags" (
+	"blog" VARCHAR NOT NULL,
+	"boosty_id" BIGINT NOT NULL,
+	"blocked" BOOLEAN NOT NULL DEFAULT FALSE,
+	"flagged_at" TIMESTAMP NOT NULL DEFAULT NOW(),
+	PRIMARY KEY ("blog", "boosty_id")
+);
@@ -0,0 +1,12 @@
+-- Your SQL goes here
+CREATE TABLE "access_events" (
+	"id" BIGSERIAL NOT NULL PRIMARY KEY,
+	"actor" VARCHAR NOT NULL,
+	"user_id" BIGINT NOT NULL,
+	"boosty_id" BIGINT,
+	"chat_id" BIGINT,
+	"decision" VARCHAR NOT NULL,
+	"reason" VARCHAR NOT NULL,
+	"created_at" TIMESTAMP NOT NULL DEFAULT NOW()
+);
+CREATE INDEX "access_events_user_id_idx" ON "access_events" ("user_id", "created_at");
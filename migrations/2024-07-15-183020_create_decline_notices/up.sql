@@ -0,0 +1,5 @@
+-- Your SQL goes here
+CREATE TABLE "decline_notices" (
+	"user_id" BIGINT NOT NULL PRIMARY KEY,
+	"sent_at" TIMESTAMP NOT NULL DEFAULT NOW()
+);
@@ -0,0 +1,9 @@
+-- Your SQL goes here
+CREATE TABLE "deferred_join_requests" (
+	"chat_id" BIGINT NOT NULL,
+	"user_id" BIGINT NOT NULL,
+	"user_chat_id" BIGINT NOT NULL,
+	"requested_at" TIMESTAMP NOT NULL DEFAULT NOW(),
+	"attempts" INTEGER NOT NULL DEFAULT 0,
+	PRIMARY KEY ("chat_id", "user_id")
+);
@@ -0,0 +1,6 @@
+-- Your SQL goes here
+CREATE TABLE "user_settings" (
+	"user_id" BIGINT NOT NULL PRIMARY KEY,
+	"locale" VARCHAR,
+	"language_code" VARCHAR
+);
@@ -0,0 +1,92 @@
+use std::{collections::BTreeSet, fs::read_to_string};
+
+use serde::Deserialize;
+
+use crate::boosty_api::types::subscribers::Subscriber;
+
+#[derive(Deserialize, Debug, Clone)]
+pub struct AccessRule {
+    pub blog: Option<String>,
+    #[serde(default)]
+    pub levels: Vec<u64>,
+    pub min_price: Option<f32>,
+    pub chats: Vec<i64>,
+}
+
+impl AccessRule {
+    fn matches(&self, blog: &str, subscriber: &Subscriber) -> bool {
+        if self.blog.as_deref().is_some_and(|value| value != blog) {
+            return false;
+        }
+
+        if !self.levels.is_empty() && !self.levels.contains(&subscriber.level.id) {
+            return false;
+        }
+
+        self.min_price
+            .is_none_or(|min_price| subscriber.level.price >= min_price)
+    }
+}
+
+#[derive(Deserialize, Debug, Clone, Default)]
+pub struct AccessMatrix {
+    #[serde(rename = "rule", default)]
+    pub rules: Vec<AccessRule>,
+}
+
+impl AccessMatrix {
+    pub fn load(path: &str) -> Self {
+        toml::from_str(
+            &read_to_string(path)
+                .unwrap_or_else(|_| panic!("Unable to read the access config `{}`.", path)),
+        )
+        .unwrap_or_else(|err| panic!("Unable to parse the access config `{}`: {}", path, err))
+    }
+
+    pub fn single_chat(chat_id: i64) -> Self {
+        Self {
+            rules: vec![AccessRule {
+                blog: None,
+                levels: vec![],
+                min_price: None,
+                chats: vec![chat_id],
+            }],
+        }
+    }
+
+    pub fn chats(&self) -> BTreeSet<i64> {
+        self.rules
+            .iter()
+            .flat_map(|rule| rule.chats.iter().copied())
+            .collect()
+    }
+
+    /// Chats that subscribers of `blog` may be granted.
+    pub fn blog_chats(&self, blog: &str) -> BTreeSet<i64> {
+        self.rules
+            .iter()
+            .filter(|rule| rule.blog.as_deref().is_none_or(|value| value == blog))
+            .flat_map(|rule| rule.chats.iter().copied())
+            .collect()
+    }
+
+    pub fn manages(&self, chat_id: i64) -> bool {
+        self.rules.iter().any(|rule| rule.chats.contains(&chat_id))
+    }
+
+    pub fn granted_chats(&self, blog: &str, subscriber: &Subscriber) -> BTreeSet<i64> {
+        if !subscriber.is_paid() {
+            return BTreeSet::new();
+        }
+
+        self.rules
+            .iter()
+            .filter(|rule| rule.matches(blog, subscriber))
+            .flat_map(|rule| rule.chats.iter().copied())
+            .collect()
+    }
+
+    pub fn grants(&self, blog: &str, subscriber: &Subscriber, chat_id: i64) -> bool {
+        self.granted_chats(blog, subscriber).contains(&chat_id)
+    }
+}
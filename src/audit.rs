@@ -0,0 +1,113 @@
+use crate::{
+    db::{access_events::create_access_event, Connection},
+    models::NewAccessEvent,
+};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Actor {
+    JoinRequest,
+    Checker,
+    Admin,
+    User,
+}
+
+impl Actor {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::JoinRequest => "join-request",
+            Self::Checker => "checker",
+            Self::Admin => "admin",
+            Self::User => "user",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Decision {
+    Approved,
+    Declined,
+    Deferred,
+    Kicked,
+    Suspended,
+    Removed,
+    Restored,
+}
+
+impl Decision {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Approved => "approved",
+            Self::Declined => "declined",
+            Self::Deferred => "deferred",
+            Self::Kicked => "kicked",
+            Self::Suspended => "suspended",
+            Self::Removed => "removed",
+            Self::Restored => "restored",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Reason {
+    Approved,
+    NotLinked,
+    ApiError,
+    NotFound,
+    Unpaid,
+    Blacklisted,
+    LevelMismatch,
+    Shared,
+    ForeignLink,
+    Unlinked,
+    Renewed,
+}
+
+impl Reason {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Approved => "approved",
+            Self::NotLinked => "not-linked",
+            Self::ApiError => "api-error",
+            Self::NotFound => "not-found",
+            Self::Unpaid => "unpaid",
+            Self::Blacklisted => "blacklisted",
+            Self::LevelMismatch => "level-mismatch",
+            Self::Shared => "shared",
+            Self::ForeignLink => "foreign-link",
+            Self::Unlinked => "unlinked",
+            Self::Renewed => "renewed",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct AccessDecision {
+    pub actor: Actor,
+    pub user_id: i64,
+    pub boosty_id: Option<i64>,
+    pub chat_id: Option<i64>,
+    pub decision: Decision,
+    pub reason: Reason,
+}
+
+pub async fn record_access(conn: &mut Connection<'_>, access_decision: AccessDecision) {
+    let res = create_access_event(
+        conn,
+        NewAccessEvent {
+            actor: access_decision.actor.as_str(),
+            user_id: access_decision.user_id,
+            boosty_id: access_decision.boosty_id,
+            chat_id: access_decision.chat_id,
+            decision: access_decision.decision.as_str(),
+            reason: access_decision.reason.as_str(),
+        },
+    )
+    .await;
+
+    if let Err(err) = res {
+        error!(
+            "Unable to record access decision for {}: {}",
+            access_decision.user_id, err
+        );
+    }
+}
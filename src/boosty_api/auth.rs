@@ -1,13 +1,10 @@
 use std::{
     fmt::Display,
-    fs::{read_to_string, write},
     time::{SystemTime, UNIX_EPOCH},
 };
 
 use serde::{de, Deserialize, Deserializer, Serialize, Serializer};
 
-const AUTH_CONFIG_FILE: &str = "auth.toml";
-
 fn current_unix_time() -> u128 {
     SystemTime::now()
         .duration_since(UNIX_EPOCH)
@@ -74,20 +71,20 @@ impl Display for AuthData {
 }
 
 impl AuthData {
-    pub fn load() -> Self {
-        toml::from_str(
-            &read_to_string(AUTH_CONFIG_FILE)
-                .expect("Unable to find auth.toml in the current directory."),
-        )
-        .expect("Unable to read the auth data file.")
-    }
-
-    pub fn save(&self) {
-        write(
-            AUTH_CONFIG_FILE,
-            toml::to_string_pretty(&self).expect("Unexpected error: unable serialize auth data."),
-        )
-        .expect("Unable to save auth data.");
+    pub fn new(
+        access_token: String,
+        refresh_token: String,
+        device_id: String,
+        expires_at: u128,
+    ) -> Self {
+        Self {
+            access_token,
+            refresh_token,
+            device_id,
+            device_os: default_device_os(),
+            grant_type: default_grant_type(),
+            expires_at,
+        }
     }
 
     pub fn expired(&self) -> bool {
@@ -0,0 +1,127 @@
+use std::{
+    env,
+    fmt::{Debug, Display},
+    io::ErrorKind,
+    path::PathBuf,
+};
+
+use async_trait::async_trait;
+use tokio::{
+    fs::{self, OpenOptions},
+    io::AsyncWriteExt,
+};
+
+use super::auth::AuthData;
+
+#[derive(Debug)]
+pub enum CredentialError {
+    Io(std::io::Error),
+    Format(String),
+    Missing(String),
+    Backend(Box<dyn std::error::Error + Send + Sync>),
+}
+
+impl Display for CredentialError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Io(err) => write!(f, "Credential storage I/O error: {}", err),
+            Self::Format(err) => write!(f, "Malformed credentials: {}", err),
+            Self::Missing(what) => write!(f, "Credentials are missing: {}", what),
+            Self::Backend(err) => write!(f, "Credential storage error: {}", err),
+        }
+    }
+}
+
+impl std::error::Error for CredentialError {}
+
+impl From<std::io::Error> for CredentialError {
+    fn from(value: std::io::Error) -> Self {
+        Self::Io(value)
+    }
+}
+
+#[async_trait]
+pub trait CredentialStore: Debug + Send + Sync {
+    async fn load(&self) -> Result<AuthData, CredentialError>;
+    async fn save(&self, auth_data: &AuthData) -> Result<(), CredentialError>;
+}
+
+#[derive(Debug, Clone)]
+pub struct FileCredentialStore {
+    path: PathBuf,
+}
+
+impl FileCredentialStore {
+    pub fn new(path: impl Into<PathBuf>) -> Self {
+        Self { path: path.into() }
+    }
+}
+
+#[async_trait]
+impl CredentialStore for FileCredentialStore {
+    async fn load(&self) -> Result<AuthData, CredentialError> {
+        let content = fs::read_to_string(&self.path).await?;
+
+        toml::from_str(&content).map_err(|err| CredentialError::Format(err.to_string()))
+    }
+
+    async fn save(&self, auth_data: &AuthData) -> Result<(), CredentialError> {
+        let content = toml::to_string_pretty(auth_data)
+            .map_err(|err| CredentialError::Format(err.to_string()))?;
+
+        let mut tmp_path = self.path.clone().into_os_string();
+        tmp_path.push(".tmp");
+
+        // A save interrupted before the rename leaves the temp file behind, and
+        // reusing it would keep whatever mode it was created with.
+        match fs::remove_file(&tmp_path).await {
+            Err(err) if err.kind() != ErrorKind::NotFound => return Err(err.into()),
+            _ => {}
+        }
+
+        let mut options = OpenOptions::new();
+        options.write(true).create_new(true);
+        // The tokens give access to the blog, so only the bot may read them.
+        #[cfg(unix)]
+        options.mode(0o600);
+
+        let mut tmp_file = options.open(&tmp_path).await?;
+        tmp_file.write_all(content.as_bytes()).await?;
+        tmp_file.sync_all().await?;
+
+        fs::rename(&tmp_path, &self.path).await?;
+
+        Ok(())
+    }
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct EnvCredentialStore;
+
+impl EnvCredentialStore {
+    fn var(name: &str) -> Result<String, CredentialError> {
+        env::var(name).map_err(|_| CredentialError::Missing(format!("`{}` is not set", name)))
+    }
+}
+
+#[async_trait]
+impl CredentialStore for EnvCredentialStore {
+    async fn load(&self) -> Result<AuthData, CredentialError> {
+        let expires_at = Self::var("BOOSTY_EXPIRES_AT")?
+            .parse()
+            .map_err(|_| CredentialError::Format("`BOOSTY_EXPIRES_AT` is not a number".into()))?;
+
+        Ok(AuthData::new(
+            Self::var("BOOSTY_ACCESS_TOKEN")?,
+            Self::var("BOOSTY_REFRESH_TOKEN")?,
+            Self::var("BOOSTY_DEVICE_ID")?,
+            expires_at,
+        ))
+    }
+
+    async fn save(&self, _auth_data: &AuthData) -> Result<(), CredentialError> {
+        warn!("Environment credential store is read-only, refreshed tokens are kept in memory.");
+
+        Ok(())
+    }
+}
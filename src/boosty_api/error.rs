@@ -0,0 +1,101 @@
+use std::{fmt::Display, time::Duration};
+
+use reqwest::StatusCode;
+
+#[derive(Debug)]
+pub enum BoostyError {
+    Transport(reqwest::Error),
+    Unauthorized {
+        status: StatusCode,
+        body: String,
+    },
+    RateLimited {
+        retry_after: Option<Duration>,
+        body: String,
+    },
+    Server {
+        status: StatusCode,
+        body: String,
+    },
+    Status {
+        status: StatusCode,
+        body: String,
+    },
+    Decode {
+        source: serde_json::Error,
+        body: String,
+    },
+    QueueFull,
+}
+
+impl BoostyError {
+    pub fn from_status(status: StatusCode, retry_after: Option<Duration>, body: String) -> Self {
+        match status {
+            StatusCode::UNAUTHORIZED | StatusCode::FORBIDDEN => Self::Unauthorized { status, body },
+            StatusCode::TOO_MANY_REQUESTS => Self::RateLimited { retry_after, body },
+            status if status.is_server_error() => Self::Server { status, body },
+            status => Self::Status { status, body },
+        }
+    }
+
+    pub fn is_transient(&self) -> bool {
+        matches!(
+            self,
+            Self::Transport(_) | Self::RateLimited { .. } | Self::Server { .. } | Self::QueueFull
+        )
+    }
+
+    pub fn is_auth(&self) -> bool {
+        matches!(self, Self::Unauthorized { .. })
+    }
+}
+
+impl Display for BoostyError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Transport(err) => write!(f, "Boosty transport error: {}", err),
+            Self::Unauthorized { status, body } => {
+                write!(f, "Boosty rejected credentials ({}): {}", status, body)
+            }
+            Self::RateLimited { retry_after, body } => match retry_after {
+                Some(delay) => write!(
+                    f,
+                    "Boosty rate limit hit, retry after {}s: {}",
+                    delay.as_secs(),
+                    body
+                ),
+                None => write!(f, "Boosty rate limit hit: {}", body),
+            },
+            Self::Server { status, body } => {
+                write!(f, "Boosty server error ({}): {}", status, body)
+            }
+            Self::Status { status, body } => {
+                write!(f, "Unexpected Boosty response ({}): {}", status, body)
+            }
+            Self::Decode { source, body } => {
+                write!(
+                    f,
+                    "Unable to decode Boosty response: {}; body: {}",
+                    source, body
+                )
+            }
+            Self::QueueFull => write!(f, "Boosty request queue is full"),
+        }
+    }
+}
+
+impl std::error::Error for BoostyError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Self::Transport(err) => Some(err),
+            Self::Decode { source, .. } => Some(source),
+            _ => None,
+        }
+    }
+}
+
+impl From<reqwest::Error> for BoostyError {
+    fn from(value: reqwest::Error) -> Self {
+        Self::Transport(value)
+    }
+}
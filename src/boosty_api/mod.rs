@@ -1,15 +1,30 @@
-use std::{fmt::Display, sync::Arc};
+use std::{fmt::Display, sync::Arc, time::Duration};
 
-use reqwest::RequestBuilder;
+use chrono::NaiveDateTime;
+use futures::{stream, Stream, StreamExt, TryStreamExt};
+use reqwest::{header::RETRY_AFTER, RequestBuilder, StatusCode};
 use serde::de::DeserializeOwned;
-use tokio::sync::RwLock;
+use tokio::{sync::RwLock, time::sleep};
 
-use self::types::{auth::*, subscribers::*};
+use self::{
+    credentials::{CredentialError, CredentialStore},
+    types::{auth::*, payments::*, subscribers::*},
+};
+
+pub use self::{
+    error::BoostyError,
+    rate_limit::{RateLimit, RateLimiter, RateLimiterMetrics},
+    retry::RetryPolicy,
+};
 
 pub mod auth;
+pub mod credentials;
+pub mod error;
+pub mod rate_limit;
+pub mod retry;
 pub mod types;
 
-pub type RequestResult<T> = Result<T, Box<dyn std::error::Error + Send + Sync>>;
+pub type RequestResult<T> = Result<T, BoostyError>;
 
 #[derive(Clone, Debug)]
 struct BaseUrl(String);
@@ -30,7 +45,10 @@ impl Display for BaseUrl {
 pub struct BoostyClient {
     base_url: BaseUrl,
     auth: Arc<RwLock<auth::AuthData>>,
+    credential_store: Option<Arc<dyn CredentialStore>>,
     client: reqwest::Client,
+    retry_policy: RetryPolicy,
+    rate_limiter: RateLimiter,
 }
 
 impl BoostyClient {
@@ -42,83 +60,234 @@ impl BoostyClient {
     where
         R: DeserializeOwned,
     {
-        Ok(request
-            .send()
-            .await
-            .unwrap()
-            .error_for_status()?
-            .json::<R>()
-            .await?)
+        self.rate_limiter.acquire().await?;
+
+        let response = request.send().await?;
+
+        let status = response.status();
+        let retry_after = response
+            .headers()
+            .get(RETRY_AFTER)
+            .and_then(|value| value.to_str().ok())
+            .and_then(|value| value.trim().parse::<u64>().ok())
+            .map(Duration::from_secs);
+
+        let body = response.text().await?;
+
+        if !status.is_success() {
+            return Err(BoostyError::from_status(status, retry_after, body));
+        }
+
+        serde_json::from_str::<R>(&body).map_err(|source| BoostyError::Decode { source, body })
     }
 
-    async fn send_request_json<R>(&self, request: RequestBuilder) -> RequestResult<R>
+    async fn send_request_json_once<R>(&self, request: RequestBuilder) -> RequestResult<R>
     where
         R: DeserializeOwned,
     {
         self.refresh_auth_if_expired().await?;
-        self.send_request_json_no_auth_check::<R>(request).await
+
+        let replay_request = request.try_clone();
+        let access_token = self.auth.read().await.access_token.clone();
+
+        let result = self
+            .send_request_json_no_auth_check::<R>(request.bearer_auth(&access_token))
+            .await;
+
+        match (result, replay_request) {
+            (
+                Err(BoostyError::Unauthorized {
+                    status: StatusCode::UNAUTHORIZED,
+                    ..
+                }),
+                Some(replay_request),
+            ) => {
+                warn!("Boosty rejected the access token, refreshing it.");
+
+                self.refresh_auth(&access_token).await?;
+                self.send_request_json_no_auth_check::<R>(
+                    self.prepare_request(replay_request).await,
+                )
+                .await
+            }
+            (result, _) => result,
+        }
+    }
+
+    async fn send_request_json<R>(&self, request: RequestBuilder) -> RequestResult<R>
+    where
+        R: DeserializeOwned,
+    {
+        let mut attempt = 0;
+
+        loop {
+            let Some(attempt_request) = request.try_clone() else {
+                return self.send_request_json_once::<R>(request).await;
+            };
+
+            let err = match self.send_request_json_once::<R>(attempt_request).await {
+                Ok(result) => return Ok(result),
+                Err(err) => err,
+            };
+
+            let Some(delay) = self.retry_policy.delay_for(attempt, &err) else {
+                return Err(err);
+            };
+
+            warn!(
+                "Boosty request failed (attempt {}), retrying in {}ms: {}",
+                attempt + 1,
+                delay.as_millis(),
+                err
+            );
+
+            sleep(delay).await;
+            attempt += 1;
+        }
+    }
+
+    pub fn rate_limiter_metrics(&self) -> RateLimiterMetrics {
+        self.rate_limiter.metrics()
     }
 
     fn full_url(&self, url: impl ToString) -> String {
         format!("{}{}", self.base_url, url.to_string())
     }
 
-    pub async fn refresh_auth_if_expired(
-        &self,
-    ) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
-        let auth_data = self.auth.read().await.clone();
+    async fn refresh_auth(&self, stale_access_token: &str) -> RequestResult<()> {
+        let mut auth_data = self.auth.write().await;
 
-        if !auth_data.expired() {
+        // Another request already refreshed the token while we were waiting for the lock.
+        if auth_data.access_token != stale_access_token {
             return Ok(());
         }
 
         let result = self
             .send_request_json_no_auth_check::<RefreshAuthDataResponse>(
-                self.prepare_request(self.client.post(self.full_url("/oauth/token/")))
-                    .await
+                self.client
+                    .post(self.full_url("/oauth/token/"))
+                    .bearer_auth(&auth_data.access_token)
                     .form(&RefreshAuthDataRequest {
-                        device_id: auth_data.device_id,
-                        device_os: auth_data.device_os,
-                        grant_type: auth_data.grant_type,
-                        refresh_token: auth_data.refresh_token,
+                        device_id: auth_data.device_id.clone(),
+                        device_os: auth_data.device_os.clone(),
+                        grant_type: auth_data.grant_type.clone(),
+                        refresh_token: auth_data.refresh_token.clone(),
                     }),
             )
             .await?;
 
-        let mut auth_data = self.auth.write().await;
-
         auth_data.access_token = result.access_token;
         auth_data.refresh_token = result.refresh_token;
         auth_data.update_from_expires_in(result.expires_in);
 
-        auth_data.save();
+        if let Some(credential_store) = &self.credential_store {
+            if let Err(err) = credential_store.save(&auth_data).await {
+                error!("Unable to persist refreshed Boosty credentials: {}", err);
+            }
+        }
 
         Ok(())
     }
 
+    pub async fn refresh_auth_if_expired(&self) -> RequestResult<()> {
+        let auth_data = self.auth.read().await;
+
+        if !auth_data.expired() {
+            return Ok(());
+        }
+
+        let stale_access_token = auth_data.access_token.clone();
+        drop(auth_data);
+
+        self.refresh_auth(&stale_access_token).await
+    }
+
     pub async fn subscribers(
         &self,
+        blog: &str,
         data: &SubscribersRequest,
     ) -> RequestResult<SubscribersResponse> {
         self.send_request_json::<SubscribersResponse>(
-            self.prepare_request(
-                self.client
-                    .get(self.full_url("/v1/blog/hedgehoginc/subscribers")),
-            )
-            .await
-            .query(data),
+            self.client
+                .get(self.full_url(format!("/v1/blog/{}/subscribers", blog)))
+                .query(data),
         )
         .await
     }
 
-    pub async fn search(&self, data: &SearchRequest) -> RequestResult<SearchResponse> {
-        self.send_request_json::<SearchResponse>(
-            self.prepare_request(
-                self.client
-                    .get(self.full_url("/v1/blog/stat/hedgehoginc/search")),
-            )
+    /// Iterates over every subscriber matching `request`, fetching the following pages
+    /// on demand. `request.limit` is used as the page size and up to `concurrency`
+    /// pages are requested at once after the first one reports the total.
+    pub fn subscribers_stream(
+        &self,
+        blog: &str,
+        request: SubscribersRequest,
+        concurrency: usize,
+    ) -> impl Stream<Item = RequestResult<Subscriber>> {
+        let client = self.clone();
+        let blog = blog.to_string();
+
+        stream::once(async move {
+            let first_page = client.subscribers(&blog, &request).await?;
+
+            let start = request.offset.unwrap_or(0);
+            let page_size = match first_page.limit {
+                0 => request.limit.max(1),
+                limit => limit,
+            };
+
+            let offsets = (start + page_size..first_page.total).step_by(page_size as usize);
+
+            let next_pages = stream::iter(offsets)
+                .map(move |offset| {
+                    let client = client.clone();
+                    let blog = blog.clone();
+                    let request = SubscribersRequest {
+                        offset: Some(offset),
+                        ..request.clone()
+                    };
+
+                    async move { client.subscribers(&blog, &request).await }
+                })
+                .buffered(concurrency.max(1));
+
+            RequestResult::Ok(stream::iter([Ok(first_page)]).chain(next_pages))
+        })
+        .try_flatten()
+        .map_ok(|page| stream::iter(page.data.into_iter().map(Ok)))
+        .try_flatten()
+    }
+
+    pub async fn payments(
+        &self,
+        blog: &str,
+        data: &PaymentsRequest,
+    ) -> RequestResult<PaymentsResponse> {
+        self.send_request_json::<PaymentsResponse>(
+            self.client
+                .get(self.full_url(format!("/v1/blog/stat/{}/payments", blog)))
+                .query(data),
+        )
+        .await
+    }
+
+    /// End of the period paid by `user_id`, covering renewals and yearly plans,
+    /// or `None` if the blog has no payments from them.
+    pub async fn paid_until(
+        &self,
+        blog: &str,
+        user_id: u64,
+    ) -> RequestResult<Option<NaiveDateTime>> {
+        self.payments(blog, &PaymentsRequest { user_id, limit: 10 })
             .await
-            .query(data),
+            .map(|payments| payments.paid_until())
+    }
+
+    pub async fn search(&self, blog: &str, data: &SearchRequest) -> RequestResult<SearchResponse> {
+        self.send_request_json::<SearchResponse>(
+            self.client
+                .get(self.full_url(format!("/v1/blog/stat/{}/search", blog)))
+                .query(data),
         )
         .await
     }
@@ -127,6 +296,9 @@ impl BoostyClient {
 pub struct BoostyClientBuilder {
     base_url: BaseUrl,
     auth: auth::AuthData,
+    credential_store: Option<Arc<dyn CredentialStore>>,
+    retry_policy: RetryPolicy,
+    rate_limit: RateLimit,
 }
 
 impl BoostyClientBuilder {
@@ -134,20 +306,52 @@ impl BoostyClientBuilder {
         Self {
             base_url: BaseUrl::default(),
             auth,
+            credential_store: None,
+            retry_policy: RetryPolicy::default(),
+            rate_limit: RateLimit::default(),
         }
     }
 
+    pub async fn from_store(
+        credential_store: Arc<dyn CredentialStore>,
+    ) -> Result<Self, CredentialError> {
+        let auth = credential_store.load().await?;
+
+        Ok(Self::new(auth).with_credential_store(credential_store))
+    }
+
+    pub fn with_credential_store(mut self, credential_store: Arc<dyn CredentialStore>) -> Self {
+        self.credential_store = Some(credential_store);
+
+        self
+    }
+
     pub fn with_custom_base_url(mut self, new_url: String) -> Self {
         self.base_url = BaseUrl(new_url);
 
         self
     }
 
+    pub fn with_retry_policy(mut self, retry_policy: RetryPolicy) -> Self {
+        self.retry_policy = retry_policy;
+
+        self
+    }
+
+    pub fn with_rate_limit(mut self, rate_limit: RateLimit) -> Self {
+        self.rate_limit = rate_limit;
+
+        self
+    }
+
     pub fn build(&self) -> BoostyClient {
         BoostyClient {
             base_url: self.base_url.clone(),
             auth: Arc::new(RwLock::new(self.auth.clone())),
+            credential_store: self.credential_store.clone(),
             client: reqwest::Client::new(),
+            retry_policy: self.retry_policy.clone(),
+            rate_limiter: RateLimiter::new(self.rate_limit.clone()),
         }
     }
 }
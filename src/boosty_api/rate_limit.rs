@@ -0,0 +1,209 @@
+use std::{
+    fmt::Display,
+    sync::{
+        atomic::{AtomicU64, AtomicUsize, Ordering},
+        Arc,
+    },
+    time::Duration,
+};
+
+use tokio::{
+    sync::Mutex,
+    time::{sleep, Instant},
+};
+
+use crate::utils::parse_env;
+
+use super::{BoostyError, RequestResult};
+
+#[derive(Clone, Debug)]
+pub struct RateLimit {
+    pub requests_per_second: f64,
+    pub burst: u32,
+    pub max_queue: usize,
+}
+
+impl Default for RateLimit {
+    fn default() -> Self {
+        Self {
+            requests_per_second: 5.,
+            burst: 5,
+            max_queue: 100,
+        }
+    }
+}
+
+impl RateLimit {
+    pub fn from_env() -> Self {
+        let default = Self::default();
+
+        let requests_per_second =
+            parse_env("BOOSTY_REQUESTS_PER_SECOND").unwrap_or(default.requests_per_second);
+        let burst = parse_env("BOOSTY_BURST").unwrap_or(default.burst);
+
+        // Either would stall every request forever instead of failing loudly here.
+        assert!(
+            requests_per_second.is_finite() && requests_per_second > 0.,
+            "Environment variable `BOOSTY_REQUESTS_PER_SECOND` must be a positive number."
+        );
+        assert!(
+            burst >= 1,
+            "Environment variable `BOOSTY_BURST` must be at least 1."
+        );
+
+        Self {
+            requests_per_second,
+            burst,
+            max_queue: parse_env("BOOSTY_QUEUE_SIZE").unwrap_or(default.max_queue),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, Default)]
+pub struct RateLimiterMetrics {
+    pub acquired: u64,
+    pub delayed: u64,
+    pub rejected: u64,
+    pub total_wait: Duration,
+    pub max_wait: Duration,
+}
+
+impl Display for RateLimiterMetrics {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let average_wait = match self.delayed {
+            0 => Duration::ZERO,
+            delayed => self.total_wait / delayed as u32,
+        };
+
+        write!(
+            f,
+            "{} acquired, {} delayed (avg {}ms, max {}ms), {} rejected",
+            self.acquired,
+            self.delayed,
+            average_wait.as_millis(),
+            self.max_wait.as_millis(),
+            self.rejected
+        )
+    }
+}
+
+#[derive(Debug)]
+struct Bucket {
+    tokens: f64,
+    updated_at: Instant,
+}
+
+impl Bucket {
+    fn refill(&mut self, limit: &RateLimit) {
+        let now = Instant::now();
+        let elapsed = now.duration_since(self.updated_at).as_secs_f64();
+
+        self.tokens = (self.tokens + elapsed * limit.requests_per_second).min(limit.burst as f64);
+        self.updated_at = now;
+    }
+}
+
+#[derive(Debug)]
+struct RateLimiterState {
+    bucket: Mutex<Bucket>,
+    queued: AtomicUsize,
+    acquired: AtomicU64,
+    delayed: AtomicU64,
+    rejected: AtomicU64,
+    total_wait_us: AtomicU64,
+    max_wait_us: AtomicU64,
+}
+
+/// A place in the queue, given back when dropped so that cancelled requests
+/// don't keep it.
+struct QueueSlot<'a>(&'a AtomicUsize);
+
+impl Drop for QueueSlot<'_> {
+    fn drop(&mut self) {
+        self.0.fetch_sub(1, Ordering::SeqCst);
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct RateLimiter {
+    limit: RateLimit,
+    state: Arc<RateLimiterState>,
+}
+
+impl RateLimiter {
+    pub fn new(limit: RateLimit) -> Self {
+        let state = RateLimiterState {
+            bucket: Mutex::new(Bucket {
+                tokens: limit.burst as f64,
+                updated_at: Instant::now(),
+            }),
+            queued: AtomicUsize::new(0),
+            acquired: AtomicU64::new(0),
+            delayed: AtomicU64::new(0),
+            rejected: AtomicU64::new(0),
+            total_wait_us: AtomicU64::new(0),
+            max_wait_us: AtomicU64::new(0),
+        };
+
+        Self {
+            limit,
+            state: Arc::new(state),
+        }
+    }
+
+    pub async fn acquire(&self) -> RequestResult<()> {
+        let state = &self.state;
+
+        if state.queued.fetch_add(1, Ordering::SeqCst) >= self.limit.max_queue {
+            state.queued.fetch_sub(1, Ordering::SeqCst);
+            state.rejected.fetch_add(1, Ordering::Relaxed);
+
+            return Err(BoostyError::QueueFull);
+        }
+
+        let slot = QueueSlot(&state.queued);
+        let started_at = Instant::now();
+
+        {
+            // Tokio's mutex is fair, so waiters are served in arrival order.
+            let mut bucket = state.bucket.lock().await;
+            bucket.refill(&self.limit);
+
+            if bucket.tokens < 1. {
+                let missing = 1. - bucket.tokens;
+                sleep(Duration::from_secs_f64(
+                    missing / self.limit.requests_per_second,
+                ))
+                .await;
+                bucket.refill(&self.limit);
+            }
+
+            bucket.tokens -= 1.;
+        }
+
+        drop(slot);
+        state.acquired.fetch_add(1, Ordering::Relaxed);
+
+        let waited_us = started_at.elapsed().as_micros() as u64;
+
+        if waited_us >= 1000 {
+            state.delayed.fetch_add(1, Ordering::Relaxed);
+            state.total_wait_us.fetch_add(waited_us, Ordering::Relaxed);
+            state.max_wait_us.fetch_max(waited_us, Ordering::Relaxed);
+        }
+
+        Ok(())
+    }
+
+    pub fn metrics(&self) -> RateLimiterMetrics {
+        let state = &self.state;
+
+        RateLimiterMetrics {
+            acquired: state.acquired.load(Ordering::Relaxed),
+            delayed: state.delayed.load(Ordering::Relaxed),
+            rejected: state.rejected.load(Ordering::Relaxed),
+            total_wait: Duration::from_micros(state.total_wait_us.load(Ordering::Relaxed)),
+            max_wait: Duration::from_micros(state.max_wait_us.load(Ordering::Relaxed)),
+        }
+    }
+}
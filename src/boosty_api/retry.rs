@@ -0,0 +1,64 @@
+use std::time::Duration;
+
+use rand::Rng;
+
+use crate::utils::parse_env;
+
+use super::BoostyError;
+
+#[derive(Clone, Debug)]
+pub struct RetryPolicy {
+    pub max_attempts: u32,
+    pub base_delay: Duration,
+    pub max_delay: Duration,
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        Self {
+            max_attempts: 4,
+            base_delay: Duration::from_millis(500),
+            max_delay: Duration::from_secs(30),
+        }
+    }
+}
+
+impl RetryPolicy {
+    pub fn from_env() -> Self {
+        let default = Self::default();
+
+        Self {
+            max_attempts: parse_env::<u32>("BOOSTY_RETRY_ATTEMPTS")
+                .map_or(default.max_attempts, |value| value.max(1)),
+            base_delay: parse_env("BOOSTY_RETRY_BASE_DELAY_MS")
+                .map_or(default.base_delay, Duration::from_millis),
+            max_delay: parse_env("BOOSTY_RETRY_MAX_DELAY_MS")
+                .map_or(default.max_delay, Duration::from_millis),
+        }
+    }
+
+    pub fn delay_for(&self, attempt: u32, err: &BoostyError) -> Option<Duration> {
+        if attempt + 1 >= self.max_attempts || !err.is_transient() {
+            return None;
+        }
+
+        if let BoostyError::RateLimited {
+            retry_after: Some(retry_after),
+            ..
+        } = err
+        {
+            // Retrying sooner than asked would only hit the limit again.
+            return (*retry_after <= self.max_delay).then_some(*retry_after);
+        }
+
+        let backoff = self
+            .base_delay
+            .saturating_mul(2u32.saturating_pow(attempt))
+            .min(self.max_delay);
+        let backoff_ms = backoff.as_millis() as u64;
+
+        Some(Duration::from_millis(
+            rand::thread_rng().gen_range(backoff_ms / 2..=backoff_ms),
+        ))
+    }
+}
@@ -0,0 +1,37 @@
+use chrono::{DateTime, Months, NaiveDateTime};
+use serde::{Deserialize, Serialize};
+
+#[derive(Serialize, Debug, Clone)]
+pub struct PaymentsRequest {
+    pub user_id: u64,
+    pub limit: u32,
+}
+
+#[derive(Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct Payment {
+    pub created_at: u64,
+    /// Number of months the payment covers, 12 for yearly plans.
+    pub period: u32,
+}
+
+impl Payment {
+    pub fn paid_until(&self) -> Option<NaiveDateTime> {
+        DateTime::from_timestamp(self.created_at as i64, 0)?
+            .naive_utc()
+            .checked_add_months(Months::new(self.period))
+    }
+}
+
+#[derive(Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct PaymentsResponse {
+    pub data: Vec<Payment>,
+}
+
+impl PaymentsResponse {
+    /// End of the latest paid period, or `None` if there are no payments.
+    pub fn paid_until(&self) -> Option<NaiveDateTime> {
+        self.data.iter().filter_map(Payment::paid_until).max()
+    }
+}
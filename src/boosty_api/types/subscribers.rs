@@ -1,3 +1,4 @@
+use chrono::{DateTime, Days, NaiveDateTime};
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 
@@ -31,6 +32,10 @@ pub struct Subscriber {
     pub is_black_listed: bool,
     pub level: SubscriptionLevel,
     pub on_time: u128,
+    /// End of the paid period, filled in from the blog's payments by
+    /// [`crate::boosty_api::BoostyClient::paid_until`].
+    #[serde(skip)]
+    pub paid_until: Option<NaiveDateTime>,
     pub payments: f32,
     pub price: f32,
     pub subscribed: bool,
@@ -41,7 +46,27 @@ pub struct Subscriber {
 
 impl Subscriber {
     pub fn is_paid(&self) -> bool {
-        self.subscribed && self.price > 0.
+        self.subscribed && !self.is_black_listed && self.price > 0.
+    }
+
+    /// Whether [`Self::expires_at`] is estimated from the subscription start
+    /// rather than taken from the payments.
+    pub fn is_expiry_approximate(&self) -> bool {
+        self.paid_until.is_none()
+    }
+
+    /// End of the paid period, or a month after the subscription start when
+    /// Boosty returned no payments for the subscriber.
+    pub fn expires_at(&self) -> NaiveDateTime {
+        if let Some(paid_until) = self.paid_until {
+            return paid_until;
+        }
+
+        DateTime::from_timestamp(self.on_time as i64, 0)
+            .unwrap_or_default()
+            .checked_add_days(Days::new(30))
+            .unwrap()
+            .naive_utc()
     }
 }
 
@@ -0,0 +1,490 @@
+use std::sync::Arc;
+
+use teloxide::{prelude::*, utils::command::BotCommands};
+
+use crate::{
+    audit::Actor,
+    boosty_api::{types::subscribers::SearchRequest, BoostyClient},
+    db::{
+        access_events::get_recent_access_events,
+        account_flags::remove_account_flag,
+        deferred_join_requests::count_deferred_join_requests,
+        link_events::remove_link_events,
+        pending_links::count_pending_links,
+        users::{
+            count_lapsed_users, count_users, get_user_by_boosty_id, get_users_by_boosty_id,
+            get_users_by_id,
+        },
+        Connection, Pool,
+    },
+    handlers::{chat_subscribers_checker, reconcile_user, ReconciliationReport},
+    messages::{render, Text},
+    models::User,
+    sharing::{record_event, LinkEventKind},
+    translations::{user_bundle, TranslationType, Translations},
+    utils::{Bot, Config},
+};
+
+use super::{fetch_subscriber, save_link, unlink_user};
+
+const RECENT_EVENTS_LIMIT: i64 = 20;
+
+#[derive(BotCommands, Clone)]
+#[command(rename_rule = "lowercase", description = "Команды администратора:")]
+pub enum AdminCommand {
+    #[command(description = "отобразить этот текст.")]
+    Admin,
+    #[command(description = "найти привязку по Telegram ID, Boosty ID или почте.")]
+    Lookup(String),
+    #[command(
+        description = "привязать Telegram ID к Boosty ID.",
+        parse_with = "split"
+    )]
+    Link { tg_id: i64, boosty_id: u64 },
+    #[command(description = "отвязать Telegram ID.")]
+    Unlink(i64),
+    #[command(
+        description = "снять отметку о передаче аккаунта с Boosty ID в блоге.",
+        parse_with = "split"
+    )]
+    Unblock { blog: String, boosty_id: u64 },
+    #[command(description = "показать последние решения о доступе для Telegram ID.")]
+    Events(i64),
+    #[command(description = "перепроверить подписку пользователя.")]
+    Recheck(i64),
+    #[command(description = "запустить проверку подписчиков.")]
+    Sync,
+    #[command(description = "показать статистику.")]
+    Stats,
+    #[command(description = "перезагрузить переводы.")]
+    Reload,
+}
+
+async fn is_admin(bot: &Bot, config: &Config, user_id: UserId) -> bool {
+    if config.admins.contains(&(user_id.0 as i64)) {
+        return true;
+    }
+
+    for chat_id in config.access.chats() {
+        if let Ok(chat_member) = bot.get_chat_member(ChatId(chat_id), user_id).await {
+            if chat_member.is_privileged() {
+                return true;
+            }
+        }
+    }
+
+    false
+}
+
+fn format_user(lang_bundle: &TranslationType, user: &User) -> String {
+    render(
+        lang_bundle,
+        Text::AdminUser {
+            tg_id: user.id,
+            boosty_id: user.boosty_id,
+            blog: user.blog.clone(),
+            expires_at: user.expires_at.format("%Y-%m-%d %H:%M:%S").to_string(),
+            state: match (user.lapsed_at, user.suspended) {
+                (_, true) => "suspended",
+                (Some(_), false) => "grace",
+                (None, false) => "active",
+            },
+        },
+    )
+}
+
+fn format_report(lang_bundle: &TranslationType, report: &ReconciliationReport) -> String {
+    render(
+        lang_bundle,
+        Text::AdminReport {
+            report: report.to_string(),
+        },
+    )
+}
+
+async fn lookup(
+    lang_bundle: &TranslationType,
+    boosty_client: &BoostyClient,
+    conn: &mut Connection<'_>,
+    config: &Config,
+    query: &str,
+) -> String {
+    let mut users = vec![];
+
+    if query.contains('@') {
+        for blog in &config.blogs {
+            let res = boosty_client
+                .search(
+                    blog,
+                    &SearchRequest {
+                        chunk: query.to_string(),
+                    },
+                )
+                .await;
+
+            match res {
+                Ok(result) => {
+                    for boosty_user in result.data.iter().filter(|value| value.email == query) {
+                        if let Ok(user) =
+                            get_user_by_boosty_id(conn, blog, boosty_user.id as i64).await
+                        {
+                            users.push(user);
+                        }
+                    }
+                }
+                Err(err) => {
+                    error!("{}", err);
+                    return render(lang_bundle, Text::BoostyApiError);
+                }
+            }
+        }
+    } else if let Ok(id) = query.parse::<i64>() {
+        users.extend(get_users_by_id(conn, id).await.unwrap());
+
+        users.extend(
+            get_users_by_boosty_id(conn, id)
+                .await
+                .unwrap()
+                .into_iter()
+                .filter(|user| user.id != id),
+        );
+    }
+
+    if users.is_empty() {
+        return render(lang_bundle, Text::AdminNotFound);
+    }
+
+    users
+        .iter()
+        .map(|user| format_user(lang_bundle, user))
+        .collect::<Vec<_>>()
+        .join("\n\n")
+}
+
+#[allow(clippy::too_many_arguments)]
+async fn link(
+    translations: &Translations,
+    lang_bundle: &TranslationType,
+    boosty_client: &BoostyClient,
+    bot: &Bot,
+    conn: &mut Connection<'_>,
+    config: &Config,
+    tg_id: i64,
+    boosty_id: u64,
+) -> String {
+    for blog in &config.blogs {
+        let boosty_user = match fetch_subscriber(boosty_client, blog, boosty_id).await {
+            Ok(Some(boosty_user)) => boosty_user,
+            Ok(None) => continue,
+            Err(err) => {
+                error!("{}", err);
+                return render(lang_bundle, Text::BoostyApiError);
+            }
+        };
+
+        if let Ok(user) = get_user_by_boosty_id(conn, blog, boosty_id as i64).await {
+            if user.id != tg_id {
+                return render(lang_bundle, Text::AdminLinkTaken { tg_id: user.id });
+            }
+        }
+
+        let user = save_link(conn, tg_id, blog, &boosty_user).await.unwrap();
+        record_event(
+            translations,
+            bot,
+            conn,
+            config,
+            blog,
+            user.boosty_id,
+            user.id,
+            LinkEventKind::Link,
+        )
+        .await;
+
+        return format!(
+            "{}\n\n{}",
+            render(
+                lang_bundle,
+                Text::AdminLinked {
+                    name: boosty_user.basic_info.name.clone(),
+                    level: boosty_user.level.name.clone(),
+                },
+            ),
+            format_user(lang_bundle, &user)
+        );
+    }
+
+    render(lang_bundle, Text::AdminNotFound)
+}
+
+async fn unlink(
+    lang_bundle: &TranslationType,
+    bot: &Bot,
+    conn: &mut Connection<'_>,
+    config: &Config,
+    tg_id: i64,
+) -> String {
+    let users = get_users_by_id(conn, tg_id).await.unwrap();
+
+    if users.is_empty() {
+        return render(lang_bundle, Text::AdminNotFound);
+    }
+
+    for user in &users {
+        unlink_user(bot, conn, config, Actor::Admin, user).await;
+    }
+
+    render(lang_bundle, Text::AdminUnlinked { tg_id })
+}
+
+/// Removes the flag and forgets the account's link events, so the users who
+/// triggered it don't count towards the next verdict.
+async fn unblock(
+    lang_bundle: &TranslationType,
+    conn: &mut Connection<'_>,
+    blog: String,
+    boosty_id: u64,
+) -> String {
+    if remove_account_flag(conn, &blog, boosty_id as i64)
+        .await
+        .unwrap()
+        == 0
+    {
+        return render(lang_bundle, Text::AdminNotFound);
+    }
+
+    remove_link_events(conn, &blog, boosty_id as i64)
+        .await
+        .unwrap();
+
+    render(lang_bundle, Text::AdminUnblocked { blog, boosty_id })
+}
+
+async fn events(lang_bundle: &TranslationType, conn: &mut Connection<'_>, tg_id: i64) -> String {
+    let access_events = get_recent_access_events(conn, tg_id, RECENT_EVENTS_LIMIT)
+        .await
+        .unwrap();
+
+    if access_events.is_empty() {
+        return render(lang_bundle, Text::AdminNotFound);
+    }
+
+    access_events
+        .iter()
+        .map(|access_event| {
+            render(
+                lang_bundle,
+                Text::AdminEvent {
+                    created_at: access_event
+                        .created_at
+                        .format("%Y-%m-%d %H:%M:%S")
+                        .to_string(),
+                    decision: access_event.decision.clone(),
+                    reason: access_event.reason.clone(),
+                    chat_id: access_event
+                        .chat_id
+                        .map_or("none".to_string(), |value| value.to_string()),
+                    actor: access_event.actor.clone(),
+                },
+            )
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+#[allow(clippy::too_many_arguments)]
+async fn recheck(
+    translations: &Translations,
+    lang_bundle: &TranslationType,
+    boosty_client: &BoostyClient,
+    bot: &Bot,
+    conn: &mut Connection<'_>,
+    config: &Config,
+    tg_id: i64,
+) -> String {
+    let users = get_users_by_id(conn, tg_id).await.unwrap();
+    let mut report = ReconciliationReport::default();
+
+    for user in users {
+        let boosty_user =
+            match fetch_subscriber(boosty_client, &user.blog, user.boosty_id as u64).await {
+                Ok(Some(boosty_user)) => boosty_user,
+                Ok(None) => continue,
+                Err(err) => {
+                    error!("{}", err);
+                    return render(lang_bundle, Text::BoostyApiError);
+                }
+            };
+
+        let res = reconcile_user(
+            translations,
+            bot,
+            conn,
+            config,
+            Actor::Admin,
+            user,
+            Some(&boosty_user),
+            &mut report,
+        )
+        .await;
+
+        if let Err(err) = res {
+            error!("Unable to reconcile {}: {}", tg_id, err);
+        }
+    }
+
+    if report.checked == 0 {
+        return render(lang_bundle, Text::AdminNotFound);
+    }
+
+    format_report(lang_bundle, &report)
+}
+
+async fn stats(
+    lang_bundle: &TranslationType,
+    boosty_client: &BoostyClient,
+    conn: &mut Connection<'_>,
+) -> String {
+    render(
+        lang_bundle,
+        Text::AdminStats {
+            total: count_users(conn).await.unwrap(),
+            in_grace: count_lapsed_users(conn, false).await.unwrap(),
+            suspended: count_lapsed_users(conn, true).await.unwrap(),
+            pending: count_pending_links(conn).await.unwrap(),
+            deferred: count_deferred_join_requests(conn).await.unwrap(),
+            rate_limiter: boosty_client.rate_limiter_metrics().to_string(),
+        },
+    )
+}
+
+pub async fn handle_admin_command(
+    translations: Arc<Translations>,
+    boosty_client: BoostyClient,
+    pool: Pool,
+    config: Arc<Config>,
+    bot: Bot,
+    msg: Message,
+    cmd: AdminCommand,
+) -> ResponseResult<()> {
+    let Some(from_user) = msg.from() else {
+        return Ok(());
+    };
+
+    let lang_bundle = user_bundle(
+        &translations,
+        &mut pool.get().await.unwrap(),
+        from_user.id.0 as i64,
+        from_user.language_code.as_deref(),
+    )
+    .await;
+
+    if !is_admin(&bot, &config, from_user.id).await {
+        bot.send_message(msg.chat.id, render(&lang_bundle, Text::AdminForbidden))
+            .await?;
+
+        return Ok(());
+    }
+
+    let text = match cmd {
+        AdminCommand::Admin => render(&lang_bundle, Text::AdminHelp),
+        AdminCommand::Lookup(query) => {
+            let mut conn = pool.get().await.unwrap();
+
+            lookup(
+                &lang_bundle,
+                &boosty_client,
+                &mut conn,
+                &config,
+                query.trim(),
+            )
+            .await
+        }
+        AdminCommand::Link { tg_id, boosty_id } => {
+            let mut conn = pool.get().await.unwrap();
+
+            link(
+                &translations,
+                &lang_bundle,
+                &boosty_client,
+                &bot,
+                &mut conn,
+                &config,
+                tg_id,
+                boosty_id,
+            )
+            .await
+        }
+        AdminCommand::Unlink(tg_id) => {
+            let mut conn = pool.get().await.unwrap();
+
+            unlink(&lang_bundle, &bot, &mut conn, &config, tg_id).await
+        }
+        AdminCommand::Unblock { blog, boosty_id } => {
+            let mut conn = pool.get().await.unwrap();
+
+            unblock(&lang_bundle, &mut conn, blog, boosty_id).await
+        }
+        AdminCommand::Events(tg_id) => {
+            let mut conn = pool.get().await.unwrap();
+
+            events(&lang_bundle, &mut conn, tg_id).await
+        }
+        AdminCommand::Recheck(tg_id) => {
+            let mut conn = pool.get().await.unwrap();
+
+            recheck(
+                &translations,
+                &lang_bundle,
+                &boosty_client,
+                &bot,
+                &mut conn,
+                &config,
+                tg_id,
+            )
+            .await
+        }
+        AdminCommand::Sync => {
+            let report =
+                chat_subscribers_checker(translations, boosty_client, pool, config, bot.clone())
+                    .await?;
+
+            format_report(&lang_bundle, &report)
+        }
+        AdminCommand::Stats => {
+            let mut conn = pool.get().await.unwrap();
+
+            stats(&lang_bundle, &boosty_client, &mut conn).await
+        }
+        AdminCommand::Reload => match translations.reload() {
+            Ok(locales) => {
+                info!("Reloaded translations: {:?}", locales);
+
+                render(
+                    &lang_bundle,
+                    Text::AdminReloaded {
+                        locales: locales
+                            .iter()
+                            .map(|locale| locale.to_string())
+                            .collect::<Vec<_>>()
+                            .join(", "),
+                    },
+                )
+            }
+            Err(err) => {
+                error!("Unable to reload translations: {}", err);
+
+                render(
+                    &lang_bundle,
+                    Text::AdminReloadFailed {
+                        error: err.to_string(),
+                    },
+                )
+            }
+        },
+    };
+
+    bot.send_message(msg.chat.id, text).await?;
+
+    Ok(())
+}
@@ -0,0 +1,78 @@
+use std::sync::Arc;
+
+use teloxide::{
+    prelude::*,
+    types::{InlineKeyboardButton, InlineKeyboardMarkup},
+};
+
+use crate::{
+    db::{user_settings::upsert_user_locale, Pool},
+    messages::{render, Text},
+    translations::{TranslationType, Translations},
+    utils::Bot,
+};
+
+pub const LANGUAGE_CALLBACK_PREFIX: &str = "language:";
+
+pub(super) async fn language_command(
+    translations: Arc<Translations>,
+    lang_bundle: Arc<TranslationType>,
+    bot: Bot,
+    msg: Message,
+) -> ResponseResult<()> {
+    let buttons = translations.locales().into_iter().map(|locale| {
+        let locale = locale.to_string();
+        let locale_bundle = translations.get(&locale).unwrap();
+
+        vec![InlineKeyboardButton::callback(
+            render(&locale_bundle, Text::LanguageName),
+            format!("{}{}", LANGUAGE_CALLBACK_PREFIX, locale),
+        )]
+    });
+
+    bot.send_message(msg.chat.id, render(&lang_bundle, Text::LanguageSelect))
+        .reply_markup(InlineKeyboardMarkup::new(buttons))
+        .await?;
+
+    Ok(())
+}
+
+pub async fn handle_language_callback(
+    translations: Arc<Translations>,
+    pool: Pool,
+    bot: Bot,
+    query: CallbackQuery,
+) -> ResponseResult<()> {
+    let Some(locale) = query
+        .data
+        .as_deref()
+        .and_then(|data| data.strip_prefix(LANGUAGE_CALLBACK_PREFIX))
+    else {
+        return Ok(());
+    };
+
+    bot.answer_callback_query(query.id.clone()).await?;
+
+    let Some(lang_bundle) = translations.get(locale) else {
+        return Ok(());
+    };
+
+    let mut conn = pool.get().await.unwrap();
+    upsert_user_locale(&mut conn, query.from.id.0 as i64, locale)
+        .await
+        .unwrap();
+
+    let text = render(&lang_bundle, Text::LanguageChanged);
+
+    match query.regular_message() {
+        Some(message) => {
+            bot.edit_message_text(message.chat.id, message.id, text)
+                .await?;
+        }
+        None => {
+            bot.send_message(query.from.id, text).await?;
+        }
+    }
+
+    Ok(())
+}
@@ -1,24 +1,50 @@
+mod admin;
+mod language;
+mod unlink;
+
 use std::sync::Arc;
 
-use chrono::{DateTime, Days};
-use fluent::FluentArgs;
+use chrono::{NaiveDateTime, Utc};
+use diesel::QueryResult;
+use rand::Rng;
 use regex::Regex;
 use teloxide::{dispatching::dialogue::GetChatId, prelude::*, utils::command::BotCommands};
 
 use crate::{
+    audit::{AccessDecision, Actor, Decision, Reason},
     boosty_api::{
-        types::subscribers::{Order, SearchRequest, SortBy, SubscribersRequest},
-        BoostyClient,
+        types::subscribers::{Order, SearchRequest, SortBy, Subscriber, SubscribersRequest},
+        BoostyClient, RequestResult,
     },
     db::{
-        users::{create_user, get_user, get_user_by_boosty_id, update_user},
-        Pool,
+        expiry_history::create_expiry_change,
+        link_changes::{get_link_change, upsert_link_change},
+        pending_links::{
+            get_last_pending_link_for_email, get_pending_link, increment_pending_link_attempts,
+            remove_pending_link, upsert_pending_link,
+        },
+        sent_reminders::remove_sent_reminders,
+        users::{
+            create_user, get_user, get_user_by_boosty_id, get_users_by_id, remove_user, update_user,
+        },
+        Connection, Pool,
     },
-    models::User,
-    translations::TranslationType,
-    utils::Bot,
+    handlers::{notify_user, remove_from_chats},
+    invite_links::{format_invite_links, issue_invite_links},
+    mailer::Mailer,
+    messages::{render, Text},
+    models::{LinkChange, NewExpiryChange, PendingLink, User},
+    sharing::{is_blocked, record_event, record_unlink, LinkEventKind, Verdict},
+    translations::{user_bundle, TranslationType, Translations},
+    utils::{Bot, Config},
 };
 
+pub use admin::{handle_admin_command, AdminCommand};
+pub use language::{handle_language_callback, LANGUAGE_CALLBACK_PREFIX};
+pub use unlink::handle_unlink_callback;
+
+pub const EMAIL_CALLBACK: &str = "email:start";
+
 #[derive(BotCommands, Clone)]
 #[command(
     rename_rule = "lowercase",
@@ -31,15 +57,24 @@ pub enum Command {
     Help,
     #[command(description = "привязать почту Boosty.")]
     Email(String),
+    #[command(description = "подтвердить привязку кодом из письма.")]
+    Verify(String),
     #[command(description = "просмотреть свой профиль.")]
     Profile,
+    #[command(description = "отвязать аккаунт Boosty.")]
+    Unlink,
+    #[command(description = "выбрать язык.")]
+    Language,
 }
 
+#[allow(clippy::too_many_arguments)]
 async fn email_command(
     raw_email: String,
     lang_bundle: Arc<TranslationType>,
     boosty_client: BoostyClient,
     pool: Pool,
+    config: Arc<Config>,
+    mailer: Arc<dyn Mailer>,
     bot: Bot,
     msg: Message,
 ) -> ResponseResult<()> {
@@ -48,21 +83,10 @@ async fn email_command(
     )
     .unwrap();
 
-    let mut args = FluentArgs::new();
-    let mut errors = vec![];
-
     let email = match email_regex.find(&raw_email) {
         Some(value) if !value.is_empty() => value.as_str(),
         _ => {
-            let text = lang_bundle.format_pattern(
-                lang_bundle
-                    .get_message("invalid-email")
-                    .unwrap()
-                    .value()
-                    .unwrap(),
-                Some(&args),
-                &mut errors,
-            );
+            let text = render(&lang_bundle, Text::InvalidEmail);
 
             let _ = bot.send_message(msg.chat_id().unwrap(), text).await;
 
@@ -71,83 +95,482 @@ async fn email_command(
     }
     .to_string();
 
-    let pattern_id = match boosty_client
-        .search(&SearchRequest {
-            chunk: email.clone(),
-        })
-        .await
+    let from_user_id = msg.from().unwrap().id.0 as i64;
+    let mut conn = pool.get().await.unwrap();
+
+    if let Some(available_at) =
+        verification_available_at(&mut conn, &config, from_user_id, &email).await
     {
-        Ok(result) if (!result.data.is_empty() && result.data.last().unwrap().email == email) => {
-            let mut conn = pool.get().await.unwrap();
+        let text = Text::VerificationCooldown {
+            available_at: available_at.format("%Y-%m-%d %H:%M:%S").to_string(),
+        };
+        bot.send_message(msg.chat.id, render(&lang_bundle, text))
+            .await?;
+
+        return Ok(());
+    }
+
+    let mut text = Text::NoUserFound {
+        email: email.clone(),
+    };
 
-            let mut pattern = "no-user-found";
-            let res = boosty_client
-                .subscribers(&SubscribersRequest {
-                    user_ids: vec![result.data.last().unwrap().id].into(),
+    for blog in &config.blogs {
+        let boosty_id = match boosty_client
+            .search(
+                blog,
+                &SearchRequest {
+                    chunk: email.clone(),
+                },
+            )
+            .await
+        {
+            Ok(result)
+                if (!result.data.is_empty() && result.data.last().unwrap().email == email) =>
+            {
+                result.data.last().unwrap().id
+            }
+            Err(err) => {
+                error!("{}", err);
+                continue;
+            }
+            _ => continue,
+        };
+
+        let res = boosty_client
+            .subscribers(
+                blog,
+                &SubscribersRequest {
+                    user_ids: vec![boosty_id].into(),
                     sort_by: SortBy::default(),
                     limit: 10,
                     offset: Some(0),
                     order: Order::default(),
-                })
-                .await;
-
-            if let Ok(user_resp) = res {
-                let boosty_user = user_resp.data.last().unwrap();
-                let user_resp = get_user(&mut conn, msg.from().unwrap().id.0 as i64).await;
-
-                if get_user_by_boosty_id(&mut conn, boosty_user.basic_info.id as i64)
-                    .await
-                    .is_ok()
-                {
-                    pattern = "user-already-exists";
-                } else if boosty_user.is_paid() {
-                    pattern = "user-found";
-                    args.set("name", boosty_user.basic_info.name.clone());
-                    args.set("level", boosty_user.level.name.clone());
-
-                    let user_data = User {
-                        id: msg.from().unwrap().id.0 as i64,
-                        boosty_id: boosty_user.basic_info.id as i64,
-                        expires_at: DateTime::from_timestamp(
-                            boosty_user.on_time.try_into().unwrap(),
-                            0,
-                        )
-                        .unwrap()
-                        .checked_add_days(Days::new(30))
-                        .unwrap()
-                        .naive_utc(),
-                    };
-
-                    if user_resp.is_ok() {
-                        update_user(&mut conn, user_data).await.unwrap();
-                    } else {
-                        create_user(&mut conn, user_data).await.unwrap();
-                    }
-                } else {
-                    pattern = "user-not-subscribed"
+                },
+            )
+            .await;
+
+        let boosty_user = match res.map(|user_resp| user_resp.data.into_iter().last()) {
+            Ok(Some(boosty_user)) => boosty_user,
+            _ => continue,
+        };
+
+        if is_blocked(&mut conn, blog, boosty_user.basic_info.id as i64).await {
+            text = Text::AccountBlocked;
+            break;
+        }
+
+        let mut relink = false;
+
+        if let Ok(linked_user) =
+            get_user_by_boosty_id(&mut conn, blog, boosty_user.basic_info.id as i64).await
+        {
+            // The same email may still lead to a link to another blog.
+            if linked_user.id == from_user_id {
+                text = Text::UserAlreadyExists;
+                continue;
+            }
+
+            relink = true;
+        }
+
+        if let Some(available_at) = relink_available_at(
+            &mut conn,
+            &config,
+            blog,
+            boosty_user.basic_info.id as i64,
+            from_user_id,
+        )
+        .await
+        {
+            text = Text::RelinkCooldown {
+                available_at: available_at.format("%Y-%m-%d %H:%M:%S").to_string(),
+            };
+            break;
+        }
+
+        if !boosty_user.is_paid() {
+            text = Text::UserNotSubscribed {
+                email: email.clone(),
+            };
+            continue;
+        }
+
+        let code = format!("{:06}", rand::thread_rng().gen_range(0..1_000_000));
+
+        upsert_pending_link(
+            &mut conn,
+            PendingLink {
+                id: from_user_id,
+                boosty_id: boosty_user.basic_info.id as i64,
+                blog: blog.clone(),
+                email: email.clone(),
+                code: code.clone(),
+                attempts: 0,
+                expires_at: (Utc::now() + config.verification_ttl).naive_utc(),
+                sent_at: Utc::now().naive_utc(),
+            },
+        )
+        .await
+        .unwrap();
+
+        let minutes = config.verification_ttl.as_secs() / 60;
+        let subject = render(&lang_bundle, Text::VerificationEmailSubject);
+        let body = render(&lang_bundle, Text::VerificationEmailBody { code, minutes });
+
+        text = match mailer.send(&email, &subject, &body).await {
+            Ok(()) if relink => Text::RelinkCodeSent,
+            Ok(()) => Text::VerificationCodeSent {
+                email: email.clone(),
+                minutes,
+            },
+            Err(err) => {
+                error!("Unable to send verification code to {}: {}", email, err);
+                remove_pending_link(&mut conn, from_user_id).await.unwrap();
+
+                Text::VerificationEmailError {
+                    email: email.clone(),
                 }
             }
+        };
+
+        break;
+    }
+
+    bot.send_message(msg.chat.id, render(&lang_bundle, text))
+        .await?;
+
+    Ok(())
+}
+
+/// Looks the subscriber up along with the end of their paid period, which is
+/// estimated when Boosty fails to return the payments.
+async fn fetch_subscriber(
+    boosty_client: &BoostyClient,
+    blog: &str,
+    boosty_id: u64,
+) -> RequestResult<Option<Subscriber>> {
+    let boosty_user = boosty_client
+        .subscribers(
+            blog,
+            &SubscribersRequest {
+                user_ids: vec![boosty_id].into(),
+                sort_by: SortBy::default(),
+                limit: 10,
+                offset: Some(0),
+                order: Order::default(),
+            },
+        )
+        .await?
+        .data
+        .into_iter()
+        .last();
+
+    let Some(mut boosty_user) = boosty_user else {
+        return Ok(None);
+    };
+
+    if boosty_user.is_paid() {
+        match boosty_client.paid_until(blog, boosty_id).await {
+            Ok(paid_until) => boosty_user.paid_until = paid_until,
+            Err(err) => warn!(
+                "Unable to fetch payments of {} on Boosty: {}",
+                boosty_id, err
+            ),
+        }
+    }
+
+    Ok(Some(boosty_user))
+}
+
+/// Returns when the Boosty account may be linked to `user_id`, or `None` if it
+/// can be linked right away. The cooldown follows the last link or unlink by
+/// another user, so unlinking doesn't hand the account over immediately.
+async fn relink_available_at(
+    conn: &mut Connection<'_>,
+    config: &Config,
+    blog: &str,
+    boosty_id: i64,
+    user_id: i64,
+) -> Option<NaiveDateTime> {
+    let link_change = get_link_change(conn, blog, boosty_id).await.ok()?;
+
+    if link_change.user_id == user_id {
+        return None;
+    }
+
+    let available_at =
+        link_change.changed_at + chrono::Duration::from_std(config.relink_cooldown).unwrap();
+
+    (available_at > Utc::now().naive_utc()).then_some(available_at)
+}
+
+/// Returns when `user_id` may be sent another verification code for `email`,
+/// or `None` if it can be sent right away. Attempts carry over to the new code
+/// until the pending link expires, so a user out of attempts waits for that.
+async fn verification_available_at(
+    conn: &mut Connection<'_>,
+    config: &Config,
+    user_id: i64,
+    email: &str,
+) -> Option<NaiveDateTime> {
+    let now = Utc::now().naive_utc();
+    let cooldown = chrono::Duration::from_std(config.verification_cooldown).unwrap();
+    let mut available_at = None;
+
+    if let Ok(pending_link) = get_pending_link(conn, user_id).await {
+        if pending_link.expires_at < now {
+            remove_pending_link(conn, user_id).await.unwrap();
+        } else if pending_link.attempts >= config.verification_attempts {
+            available_at = Some(pending_link.expires_at);
+        } else {
+            available_at = Some(pending_link.sent_at + cooldown);
+        }
+    }
+
+    if let Some(pending_link) = get_last_pending_link_for_email(conn, email, now - cooldown)
+        .await
+        .unwrap()
+    {
+        available_at = available_at.max(Some(pending_link.sent_at + cooldown));
+    }
+
+    available_at.filter(|available_at| *available_at > now)
+}
+
+/// Removes the user's link to a blog along with their membership in the chats
+/// of that blog, which nothing would check once the link is gone.
+async fn unlink_user(
+    bot: &Bot,
+    conn: &mut Connection<'_>,
+    config: &Config,
+    actor: Actor,
+    user: &User,
+) {
+    let access = AccessDecision {
+        actor,
+        user_id: user.id,
+        boosty_id: Some(user.boosty_id),
+        chat_id: None,
+        decision: Decision::Kicked,
+        reason: Reason::Unlinked,
+    };
+
+    remove_from_chats(bot, conn, access, &config.access.blog_chats(&user.blog)).await;
+
+    remove_user(conn, user.id, &user.blog).await.unwrap();
+    remove_sent_reminders(conn, user.id).await.unwrap();
+    remove_pending_link(conn, user.id).await.unwrap();
+    record_unlink(conn, &user.blog, user.boosty_id, user.id).await;
+
+    upsert_link_change(
+        conn,
+        LinkChange {
+            blog: user.blog.clone(),
+            boosty_id: user.boosty_id,
+            user_id: user.id,
+            changed_at: Utc::now().naive_utc(),
+        },
+    )
+    .await
+    .unwrap();
+}
+
+async fn save_link(
+    conn: &mut Connection<'_>,
+    user_id: i64,
+    blog: &str,
+    boosty_user: &Subscriber,
+) -> QueryResult<User> {
+    let user_data = User {
+        id: user_id,
+        boosty_id: boosty_user.basic_info.id as i64,
+        expires_at: boosty_user.expires_at(),
+        blog: blog.to_string(),
+        lapsed_at: None,
+        suspended: false,
+    };
+    let new_expires_at = user_data.expires_at;
+
+    let (user, old_expires_at) = match get_user(conn, user_id, blog).await {
+        Ok(old_user) => (
+            update_user(conn, user_data).await?,
+            Some(old_user.expires_at),
+        ),
+        Err(_) => (create_user(conn, user_data).await?, None),
+    };
+
+    create_expiry_change(
+        conn,
+        NewExpiryChange {
+            user_id,
+            old_expires_at,
+            new_expires_at,
+        },
+    )
+    .await?;
+
+    upsert_link_change(
+        conn,
+        LinkChange {
+            blog: blog.to_string(),
+            boosty_id: user.boosty_id,
+            user_id,
+            changed_at: Utc::now().naive_utc(),
+        },
+    )
+    .await?;
+
+    Ok(user)
+}
+
+async fn link_verified_user(
+    translations: &Translations,
+    boosty_client: &BoostyClient,
+    config: &Config,
+    bot: &Bot,
+    conn: &mut Connection<'_>,
+    pending_link: &PendingLink,
+) -> Text {
+    let res = fetch_subscriber(
+        boosty_client,
+        &pending_link.blog,
+        pending_link.boosty_id as u64,
+    )
+    .await;
 
-            pattern
+    let boosty_user = match res {
+        Ok(Some(boosty_user)) => boosty_user,
+        Ok(None) => {
+            return Text::NoUserFound {
+                email: pending_link.email.clone(),
+            }
         }
         Err(err) => {
-            error!("{}", err.to_string());
-            "no-user-found"
+            error!("{}", err);
+            return Text::BoostyApiError;
         }
-        _ => "no-user-found",
     };
 
-    let pattern = lang_bundle
-        .get_message(pattern_id)
-        .unwrap_or_else(|| panic!("Message with identifier `{}` doesn't exist.", pattern_id))
-        .value()
-        .unwrap_or_else(|| panic!("Message with identifier `{}` has empty value.", pattern_id));
+    if !boosty_user.is_paid() {
+        return Text::UserNotSubscribed {
+            email: pending_link.email.clone(),
+        };
+    }
+
+    if is_blocked(conn, &pending_link.blog, pending_link.boosty_id).await {
+        return Text::AccountBlocked;
+    }
+
+    if let Some(available_at) = relink_available_at(
+        conn,
+        config,
+        &pending_link.blog,
+        pending_link.boosty_id,
+        pending_link.id,
+    )
+    .await
+    {
+        return Text::RelinkCooldown {
+            available_at: available_at.format("%Y-%m-%d %H:%M:%S").to_string(),
+        };
+    }
+
+    let linked_user = get_user_by_boosty_id(conn, &pending_link.blog, pending_link.boosty_id)
+        .await
+        .ok()
+        .filter(|linked_user| linked_user.id != pending_link.id);
+
+    let verdict = record_event(
+        translations,
+        bot,
+        conn,
+        config,
+        &pending_link.blog,
+        pending_link.boosty_id,
+        pending_link.id,
+        LinkEventKind::Link,
+    )
+    .await;
+
+    if verdict == Verdict::Block {
+        return Text::AccountBlocked;
+    }
+
+    if let Some(linked_user) = linked_user {
+        unlink_user(bot, conn, config, Actor::User, &linked_user).await;
+        notify_user(translations, bot, conn, linked_user.id, Text::AccountMoved).await;
+    }
+
+    save_link(conn, pending_link.id, &pending_link.blog, &boosty_user)
+        .await
+        .unwrap();
+
+    let granted_chats = config
+        .access
+        .granted_chats(&pending_link.blog, &boosty_user);
+    let invite_links = format_invite_links(
+        issue_invite_links(bot, conn, config, pending_link.id, &granted_chats).await,
+    );
+
+    Text::UserFound {
+        email: pending_link.email.clone(),
+        name: boosty_user.basic_info.name.clone(),
+        level: boosty_user.level.name.clone(),
+        invite_links,
+    }
+}
+
+#[allow(clippy::too_many_arguments)]
+async fn verify_command(
+    raw_code: String,
+    translations: Arc<Translations>,
+    lang_bundle: Arc<TranslationType>,
+    boosty_client: BoostyClient,
+    pool: Pool,
+    config: Arc<Config>,
+    bot: Bot,
+    msg: Message,
+) -> ResponseResult<()> {
+    let from_user_id = msg.from().unwrap().id.0 as i64;
+    let mut conn = pool.get().await.unwrap();
+
+    let text = match get_pending_link(&mut conn, from_user_id).await {
+        Err(_) => Text::NoPendingVerification,
+        Ok(pending_link) if pending_link.expires_at < Utc::now().naive_utc() => {
+            remove_pending_link(&mut conn, from_user_id).await.unwrap();
 
-    args.set("email", email);
+            Text::VerificationExpired
+        }
+        // Kept until it expires so that /email can't hand out fresh attempts.
+        Ok(pending_link) if pending_link.attempts >= config.verification_attempts => {
+            Text::VerificationExpired
+        }
+        Ok(pending_link) if pending_link.code != raw_code => {
+            let pending_link = increment_pending_link_attempts(&mut conn, from_user_id)
+                .await
+                .unwrap();
 
-    let text = lang_bundle.format_pattern(pattern, Some(&args), &mut errors);
+            Text::VerificationInvalidCode {
+                attempts_left: config.verification_attempts - pending_link.attempts,
+            }
+        }
+        Ok(pending_link) => {
+            remove_pending_link(&mut conn, from_user_id).await.unwrap();
+
+            link_verified_user(
+                &translations,
+                &boosty_client,
+                &config,
+                &bot,
+                &mut conn,
+                &pending_link,
+            )
+            .await
+        }
+    };
 
-    bot.send_message(msg.chat.id, text).await?;
+    bot.send_message(msg.chat.id, render(&lang_bundle, text))
+        .await?;
 
     Ok(())
 }
@@ -156,78 +579,83 @@ async fn profile_command(
     lang_bundle: Arc<TranslationType>,
     boosty_client: BoostyClient,
     pool: Pool,
+    config: Arc<Config>,
     bot: Bot,
     msg: Message,
 ) -> ResponseResult<()> {
     let from_user = msg.from().unwrap();
 
     let mut conn = pool.get().await.unwrap();
-    let user_resp = get_user(&mut conn, from_user.id.0 as i64).await;
+    let users = get_users_by_id(&mut conn, from_user.id.0 as i64)
+        .await
+        .unwrap_or_default();
 
-    let mut args = FluentArgs::new();
-    let mut errors = vec![];
+    if users.is_empty() {
+        bot.send_message(msg.chat.id, render(&lang_bundle, Text::NoProfile))
+            .await?;
+    }
 
-    let pattern_id;
+    // One profile per linked blog.
+    for user in users {
+        let res = fetch_subscriber(&boosty_client, &user.blog, user.boosty_id as u64).await;
 
-    if let Ok(user) = user_resp {
-        let res = boosty_client
-            .subscribers(&SubscribersRequest {
-                user_ids: vec![user.boosty_id as u64].into(),
-                sort_by: SortBy::default(),
-                limit: 10,
-                offset: Some(0),
-                order: Order::default(),
-            })
-            .await;
-
-        pattern_id = match res {
-            Ok(boosty_users)
-                if (!boosty_users.data.is_empty()
-                    && boosty_users.data.last().unwrap().price > 0.) =>
-            {
-                let boosty_user = boosty_users.data.last().unwrap();
-
-                args.set("name", boosty_user.basic_info.name.clone());
-                args.set("email", boosty_user.basic_info.email.clone());
-                args.set("level", boosty_user.level.name.clone());
-                args.set("price", boosty_user.level.price);
-                args.set(
-                    "expires-at",
-                    user.expires_at.format("%Y-%m-%d %H:%M:%S").to_string(),
+        let text = match res {
+            Ok(Some(boosty_user)) if boosty_user.price > 0. => {
+                let granted_chats = config.access.granted_chats(&user.blog, &boosty_user);
+                let invite_links = format_invite_links(
+                    issue_invite_links(&bot, &mut conn, &config, user.id, &granted_chats).await,
                 );
 
-                "profile"
+                Text::Profile {
+                    name: boosty_user.basic_info.name.clone(),
+                    email: boosty_user.basic_info.email.clone(),
+                    level: boosty_user.level.name.clone(),
+                    price: boosty_user.level.price,
+                    expires_at: boosty_user
+                        .expires_at()
+                        .format("%Y-%m-%d %H:%M:%S")
+                        .to_string(),
+                    approximate: if boosty_user.is_expiry_approximate() {
+                        "yes"
+                    } else {
+                        "no"
+                    },
+                    invite_links,
+                }
             }
-            Ok(_) => "no_profile",
-            Err(_) => "profile-api-error",
+            Ok(_) => Text::NoProfile,
+            Err(_) => Text::ProfileApiError,
         };
-    } else {
-        pattern_id = "no-profile";
-    }
-
-    let pattern = lang_bundle
-        .get_message(pattern_id)
-        .unwrap_or_else(|| panic!("Message with identifier `{}` doesn't exist.", pattern_id))
-        .value()
-        .unwrap_or_else(|| panic!("Message with identifier `{}` has empty value.", pattern_id));
 
-    let text = lang_bundle.format_pattern(pattern, Some(&args), &mut errors);
-    bot.send_message(msg.chat.id, text).await?;
+        bot.send_message(msg.chat.id, render(&lang_bundle, text))
+            .await?;
+    }
 
     Ok(())
 }
 
+#[allow(clippy::too_many_arguments)]
 async fn _handle_command(
-    lang_bundle: Arc<TranslationType>,
+    translations: Arc<Translations>,
     boosty_client: BoostyClient,
     pool: Pool,
+    config: Arc<Config>,
+    mailer: Arc<dyn Mailer>,
     bot: Bot,
     msg: Message,
     cmd: Option<Command>,
 ) -> ResponseResult<()> {
-    if msg.from().is_none() {
+    let Some(from_user) = msg.from() else {
         return Ok(());
-    }
+    };
+
+    let lang_bundle = user_bundle(
+        &translations,
+        &mut pool.get().await.unwrap(),
+        from_user.id.0 as i64,
+        from_user.language_code.as_deref(),
+    )
+    .await;
 
     let unpacked_cmd = match cmd {
         Some(value) => value,
@@ -237,20 +665,11 @@ async fn _handle_command(
 
     match unpacked_cmd {
         Command::Start => {
-            let pattern = lang_bundle
-                .get_message("start")
-                .expect("Message with identifier `start` doesn't exist.")
-                .value()
-                .expect("Message with identifier `start` has no value.");
-
-            let text = lang_bundle
-                .format_pattern(pattern, Some(&FluentArgs::new()), &mut vec![])
-                .clone();
-
-            bot.send_message(msg.chat.id, text).await?;
+            bot.send_message(msg.chat.id, render(&lang_bundle, Text::Start))
+                .await?;
         }
         Command::Help => {
-            bot.send_message(msg.chat.id, Command::descriptions().to_string())
+            bot.send_message(msg.chat.id, render(&lang_bundle, Text::Help))
                 .await?;
         }
         Command::Email(email) => {
@@ -259,36 +678,104 @@ async fn _handle_command(
                 lang_bundle,
                 boosty_client,
                 pool,
+                config,
+                mailer,
+                bot,
+                msg,
+            )
+            .await?;
+        }
+        Command::Verify(code) => {
+            verify_command(
+                code.trim().to_string(),
+                translations,
+                lang_bundle,
+                boosty_client,
+                pool,
+                config,
                 bot,
                 msg,
             )
             .await?;
         }
         Command::Profile => {
-            profile_command(lang_bundle, boosty_client, pool, bot, msg).await?;
+            profile_command(lang_bundle, boosty_client, pool, config, bot, msg).await?;
+        }
+        Command::Unlink => {
+            unlink::unlink_command(lang_bundle, pool, bot, msg).await?;
+        }
+        Command::Language => {
+            language::language_command(translations, lang_bundle, bot, msg).await?;
         }
     };
 
     Ok(())
 }
 
+pub async fn handle_email_callback(
+    translations: Arc<Translations>,
+    pool: Pool,
+    bot: Bot,
+    query: CallbackQuery,
+) -> ResponseResult<()> {
+    bot.answer_callback_query(query.id.clone()).await?;
+
+    let lang_bundle = user_bundle(
+        &translations,
+        &mut pool.get().await.unwrap(),
+        query.from.id.0 as i64,
+        query.from.language_code.as_deref(),
+    )
+    .await;
+
+    bot.send_message(query.from.id, render(&lang_bundle, Text::Start))
+        .await?;
+
+    Ok(())
+}
+
+#[allow(clippy::too_many_arguments)]
 pub async fn handle_command(
-    lang_bundle: Arc<TranslationType>,
+    translations: Arc<Translations>,
     boosty_client: BoostyClient,
     pool: Pool,
+    config: Arc<Config>,
+    mailer: Arc<dyn Mailer>,
     bot: Bot,
     msg: Message,
     cmd: Command,
 ) -> ResponseResult<()> {
-    _handle_command(lang_bundle, boosty_client, pool, bot, msg, Some(cmd)).await
+    _handle_command(
+        translations,
+        boosty_client,
+        pool,
+        config,
+        mailer,
+        bot,
+        msg,
+        Some(cmd),
+    )
+    .await
 }
 
 pub async fn handle_unknown_command(
-    lang_bundle: Arc<TranslationType>,
+    translations: Arc<Translations>,
     boosty_client: BoostyClient,
     pool: Pool,
+    config: Arc<Config>,
+    mailer: Arc<dyn Mailer>,
     bot: Bot,
     msg: Message,
 ) -> ResponseResult<()> {
-    _handle_command(lang_bundle, boosty_client, pool, bot, msg, None).await
+    _handle_command(
+        translations,
+        boosty_client,
+        pool,
+        config,
+        mailer,
+        bot,
+        msg,
+        None,
+    )
+    .await
 }
@@ -0,0 +1,114 @@
+use std::sync::Arc;
+
+use teloxide::{
+    prelude::*,
+    types::{InlineKeyboardButton, InlineKeyboardMarkup},
+};
+
+use crate::{
+    audit::Actor,
+    db::{users::get_users_by_id, Pool},
+    messages::{render, Text},
+    translations::{user_bundle, TranslationType, Translations},
+    utils::{Bot, Config},
+};
+
+use super::unlink_user;
+
+const UNLINK_CONFIRM: &str = "unlink:confirm";
+// Sent by keyboards from before unlinking always removed the membership.
+const UNLINK_LEAVE: &str = "unlink:leave";
+const UNLINK_CANCEL: &str = "unlink:cancel";
+
+pub(super) async fn unlink_command(
+    lang_bundle: Arc<TranslationType>,
+    pool: Pool,
+    bot: Bot,
+    msg: Message,
+) -> ResponseResult<()> {
+    let from_user = msg.from().unwrap();
+
+    let mut conn = pool.get().await.unwrap();
+
+    let users = get_users_by_id(&mut conn, from_user.id.0 as i64)
+        .await
+        .unwrap_or_default();
+
+    if users.is_empty() {
+        bot.send_message(msg.chat.id, render(&lang_bundle, Text::NoProfile))
+            .await?;
+
+        return Ok(());
+    }
+
+    let button =
+        |text, data: &str| InlineKeyboardButton::callback(render(&lang_bundle, text), data);
+
+    let keyboard = InlineKeyboardMarkup::new([
+        vec![button(Text::UnlinkButtonConfirm, UNLINK_CONFIRM)],
+        vec![button(Text::UnlinkButtonCancel, UNLINK_CANCEL)],
+    ]);
+
+    bot.send_message(msg.chat.id, render(&lang_bundle, Text::UnlinkConfirm))
+        .reply_markup(keyboard)
+        .await?;
+
+    Ok(())
+}
+
+pub async fn handle_unlink_callback(
+    translations: Arc<Translations>,
+    pool: Pool,
+    config: Arc<Config>,
+    bot: Bot,
+    query: CallbackQuery,
+) -> ResponseResult<()> {
+    let confirmed = match query.data.as_deref() {
+        Some(UNLINK_CONFIRM | UNLINK_LEAVE) => true,
+        Some(UNLINK_CANCEL) => false,
+        _ => return Ok(()),
+    };
+
+    bot.answer_callback_query(query.id.clone()).await?;
+
+    let lang_bundle = user_bundle(
+        &translations,
+        &mut pool.get().await.unwrap(),
+        query.from.id.0 as i64,
+        query.from.language_code.as_deref(),
+    )
+    .await;
+
+    let text = if confirmed {
+        let mut conn = pool.get().await.unwrap();
+
+        let users = get_users_by_id(&mut conn, query.from.id.0 as i64)
+            .await
+            .unwrap_or_default();
+
+        if users.is_empty() {
+            Text::NoProfile
+        } else {
+            for user in &users {
+                unlink_user(&bot, &mut conn, &config, Actor::User, user).await;
+            }
+
+            Text::UnlinkDone
+        }
+    } else {
+        Text::UnlinkCancelled
+    };
+    let text = render(&lang_bundle, text);
+
+    match query.regular_message() {
+        Some(message) => {
+            bot.edit_message_text(message.chat.id, message.id, text)
+                .await?;
+        }
+        None => {
+            bot.send_message(query.from.id, text).await?;
+        }
+    }
+
+    Ok(())
+}
@@ -0,0 +1,34 @@
+use diesel::{insert_into, prelude::*};
+use diesel_async::RunQueryDsl;
+
+use crate::{
+    models::{AccessEvent, NewAccessEvent},
+    schema::access_events,
+};
+
+use super::Connection;
+
+pub async fn create_access_event<'a>(
+    conn: &mut Connection<'a>,
+    event: NewAccessEvent<'_>,
+) -> QueryResult<AccessEvent> {
+    insert_into(access_events::table)
+        .values(event)
+        .returning(AccessEvent::as_returning())
+        .get_result(conn)
+        .await
+}
+
+pub async fn get_recent_access_events<'a>(
+    conn: &mut Connection<'a>,
+    user_id: i64,
+    limit: i64,
+) -> QueryResult<Vec<AccessEvent>> {
+    access_events::table
+        .filter(access_events::user_id.eq(user_id))
+        .order((access_events::created_at.desc(), access_events::id.desc()))
+        .limit(limit)
+        .select(AccessEvent::as_select())
+        .load(conn)
+        .await
+}
@@ -0,0 +1,44 @@
+use diesel::{delete, insert_into, prelude::*, upsert::excluded};
+use diesel_async::RunQueryDsl;
+
+use crate::{models::AccountFlag, schema::account_flags};
+
+use super::Connection;
+
+pub async fn upsert_account_flag<'a>(
+    conn: &mut Connection<'a>,
+    account_flag: AccountFlag,
+) -> QueryResult<AccountFlag> {
+    insert_into(account_flags::table)
+        .values(account_flag)
+        .on_conflict((account_flags::blog, account_flags::boosty_id))
+        .do_update()
+        .set((
+            account_flags::blocked.eq(excluded(account_flags::blocked)),
+            account_flags::flagged_at.eq(excluded(account_flags::flagged_at)),
+        ))
+        .returning(AccountFlag::as_returning())
+        .get_result(conn)
+        .await
+}
+
+pub async fn get_account_flag<'a>(
+    conn: &mut Connection<'a>,
+    blog: &str,
+    boosty_id: i64,
+) -> QueryResult<AccountFlag> {
+    account_flags::table
+        .find((blog, boosty_id))
+        .first(conn)
+        .await
+}
+
+pub async fn remove_account_flag<'a>(
+    conn: &mut Connection<'a>,
+    blog: &str,
+    boosty_id: i64,
+) -> QueryResult<usize> {
+    delete(account_flags::table.find((blog, boosty_id)))
+        .execute(conn)
+        .await
+}
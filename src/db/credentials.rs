@@ -0,0 +1,116 @@
+use async_trait::async_trait;
+use diesel::{dsl::now, insert_into, prelude::*, upsert::excluded};
+use diesel_async::RunQueryDsl;
+
+use crate::{
+    boosty_api::{
+        auth::AuthData,
+        credentials::{CredentialError, CredentialStore},
+    },
+    models::BoostyCredentials,
+    schema::boosty_credentials,
+};
+
+use super::{Connection, Pool};
+
+pub async fn get_credentials<'a>(
+    conn: &mut Connection<'a>,
+    name: &str,
+) -> QueryResult<BoostyCredentials> {
+    boosty_credentials::table
+        .filter(boosty_credentials::name.eq(name))
+        .select(BoostyCredentials::as_select())
+        .first(conn)
+        .await
+}
+
+pub async fn upsert_credentials<'a>(
+    conn: &mut Connection<'a>,
+    credentials: BoostyCredentials,
+) -> QueryResult<usize> {
+    insert_into(boosty_credentials::table)
+        .values(credentials)
+        .on_conflict(boosty_credentials::name)
+        .do_update()
+        .set((
+            boosty_credentials::access_token.eq(excluded(boosty_credentials::access_token)),
+            boosty_credentials::refresh_token.eq(excluded(boosty_credentials::refresh_token)),
+            boosty_credentials::device_id.eq(excluded(boosty_credentials::device_id)),
+            boosty_credentials::expires_at.eq(excluded(boosty_credentials::expires_at)),
+            boosty_credentials::updated_at.eq(now),
+        ))
+        .execute(conn)
+        .await
+}
+
+#[derive(Clone)]
+pub struct PgCredentialStore {
+    pool: Pool,
+    name: String,
+}
+
+impl PgCredentialStore {
+    pub fn new(pool: Pool, name: impl Into<String>) -> Self {
+        Self {
+            pool,
+            name: name.into(),
+        }
+    }
+}
+
+impl std::fmt::Debug for PgCredentialStore {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("PgCredentialStore")
+            .field("name", &self.name)
+            .finish()
+    }
+}
+
+#[async_trait]
+impl CredentialStore for PgCredentialStore {
+    async fn load(&self) -> Result<AuthData, CredentialError> {
+        let mut conn = self
+            .pool
+            .get()
+            .await
+            .map_err(|err| CredentialError::Backend(Box::new(err)))?;
+
+        let credentials = get_credentials(&mut conn, &self.name)
+            .await
+            .optional()
+            .map_err(|err| CredentialError::Backend(Box::new(err)))?
+            .ok_or_else(|| {
+                CredentialError::Missing(format!("no `{}` row in `boosty_credentials`", self.name))
+            })?;
+
+        Ok(AuthData::new(
+            credentials.access_token,
+            credentials.refresh_token,
+            credentials.device_id,
+            credentials.expires_at as u128,
+        ))
+    }
+
+    async fn save(&self, auth_data: &AuthData) -> Result<(), CredentialError> {
+        let mut conn = self
+            .pool
+            .get()
+            .await
+            .map_err(|err| CredentialError::Backend(Box::new(err)))?;
+
+        upsert_credentials(
+            &mut conn,
+            BoostyCredentials {
+                name: self.name.clone(),
+                access_token: auth_data.access_token.clone(),
+                refresh_token: auth_data.refresh_token.clone(),
+                device_id: auth_data.device_id.clone(),
+                expires_at: auth_data.expires_at as i64,
+            },
+        )
+        .await
+        .map_err(|err| CredentialError::Backend(Box::new(err)))?;
+
+        Ok(())
+    }
+}
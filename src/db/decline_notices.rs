@@ -0,0 +1,35 @@
+use chrono::NaiveDateTime;
+use diesel::{insert_into, prelude::*, update};
+use diesel_async::RunQueryDsl;
+
+use crate::{models::DeclineNotice, schema::decline_notices};
+
+use super::Connection;
+
+/// Stores the notice unless one was sent to the user after `not_before`,
+/// returning whether the caller may send it.
+pub async fn claim_decline_notice<'a>(
+    conn: &mut Connection<'a>,
+    notice: DeclineNotice,
+    not_before: NaiveDateTime,
+) -> QueryResult<bool> {
+    let updated = update(
+        decline_notices::table
+            .find(notice.user_id)
+            .filter(decline_notices::sent_at.lt(not_before)),
+    )
+    .set(decline_notices::sent_at.eq(notice.sent_at))
+    .execute(conn)
+    .await?;
+
+    if updated > 0 {
+        return Ok(true);
+    }
+
+    insert_into(decline_notices::table)
+        .values(notice)
+        .on_conflict_do_nothing()
+        .execute(conn)
+        .await
+        .map(|count| count > 0)
+}
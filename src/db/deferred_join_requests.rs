@@ -0,0 +1,51 @@
+use diesel::{delete, insert_into, prelude::*, update};
+use diesel_async::RunQueryDsl;
+
+use crate::{models::DeferredJoinRequest, schema::deferred_join_requests};
+
+use super::Connection;
+
+pub async fn create_deferred_join_request<'a>(
+    conn: &mut Connection<'a>,
+    request: DeferredJoinRequest,
+) -> QueryResult<usize> {
+    insert_into(deferred_join_requests::table)
+        .values(request)
+        .on_conflict_do_nothing()
+        .execute(conn)
+        .await
+}
+
+pub async fn get_deferred_join_requests<'a>(
+    conn: &mut Connection<'a>,
+) -> QueryResult<Vec<DeferredJoinRequest>> {
+    deferred_join_requests::table
+        .order(deferred_join_requests::requested_at)
+        .load(conn)
+        .await
+}
+
+pub async fn increment_deferred_join_request_attempts<'a>(
+    conn: &mut Connection<'a>,
+    chat_id: i64,
+    user_id: i64,
+) -> QueryResult<usize> {
+    update(deferred_join_requests::table.find((chat_id, user_id)))
+        .set(deferred_join_requests::attempts.eq(deferred_join_requests::attempts + 1))
+        .execute(conn)
+        .await
+}
+
+pub async fn remove_deferred_join_request<'a>(
+    conn: &mut Connection<'a>,
+    chat_id: i64,
+    user_id: i64,
+) -> QueryResult<usize> {
+    delete(deferred_join_requests::table.find((chat_id, user_id)))
+        .execute(conn)
+        .await
+}
+
+pub async fn count_deferred_join_requests<'a>(conn: &mut Connection<'a>) -> QueryResult<i64> {
+    deferred_join_requests::table.count().get_result(conn).await
+}
@@ -0,0 +1,20 @@
+use diesel::{insert_into, prelude::*};
+use diesel_async::RunQueryDsl;
+
+use crate::{
+    models::{ExpiryChange, NewExpiryChange},
+    schema::expiry_history,
+};
+
+use super::Connection;
+
+pub async fn create_expiry_change<'a>(
+    conn: &mut Connection<'a>,
+    change: NewExpiryChange,
+) -> QueryResult<ExpiryChange> {
+    insert_into(expiry_history::table)
+        .values(change)
+        .returning(ExpiryChange::as_returning())
+        .get_result(conn)
+        .await
+}
@@ -0,0 +1,56 @@
+use diesel::{delete, insert_into, prelude::*, upsert::excluded};
+use diesel_async::RunQueryDsl;
+
+use crate::{models::InviteLink, schema::invite_links};
+
+use super::Connection;
+
+pub async fn upsert_invite_link<'a>(
+    conn: &mut Connection<'a>,
+    invite_link: InviteLink,
+) -> QueryResult<InviteLink> {
+    insert_into(invite_links::table)
+        .values(invite_link)
+        .on_conflict((invite_links::user_id, invite_links::chat_id))
+        .do_update()
+        .set((
+            invite_links::invite_link.eq(excluded(invite_links::invite_link)),
+            invite_links::expires_at.eq(excluded(invite_links::expires_at)),
+        ))
+        .returning(InviteLink::as_returning())
+        .get_result(conn)
+        .await
+}
+
+pub async fn get_invite_link<'a>(
+    conn: &mut Connection<'a>,
+    user_id: i64,
+    chat_id: i64,
+) -> QueryResult<InviteLink> {
+    invite_links::table
+        .find((user_id, chat_id))
+        .first(conn)
+        .await
+}
+
+pub async fn get_invite_link_by_url<'a>(
+    conn: &mut Connection<'a>,
+    chat_id: i64,
+    invite_link: &str,
+) -> QueryResult<InviteLink> {
+    invite_links::table
+        .filter(invite_links::chat_id.eq(chat_id))
+        .filter(invite_links::invite_link.eq(invite_link))
+        .first(conn)
+        .await
+}
+
+pub async fn remove_invite_link<'a>(
+    conn: &mut Connection<'a>,
+    user_id: i64,
+    chat_id: i64,
+) -> QueryResult<usize> {
+    delete(invite_links::table.find((user_id, chat_id)))
+        .execute(conn)
+        .await
+}
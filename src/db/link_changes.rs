@@ -0,0 +1,34 @@
+use diesel::{insert_into, prelude::*, upsert::excluded};
+use diesel_async::RunQueryDsl;
+
+use crate::{models::LinkChange, schema::link_changes};
+
+use super::Connection;
+
+pub async fn upsert_link_change<'a>(
+    conn: &mut Connection<'a>,
+    link_change: LinkChange,
+) -> QueryResult<LinkChange> {
+    insert_into(link_changes::table)
+        .values(link_change)
+        .on_conflict((link_changes::blog, link_changes::boosty_id))
+        .do_update()
+        .set((
+            link_changes::user_id.eq(excluded(link_changes::user_id)),
+            link_changes::changed_at.eq(excluded(link_changes::changed_at)),
+        ))
+        .returning(LinkChange::as_returning())
+        .get_result(conn)
+        .await
+}
+
+pub async fn get_link_change<'a>(
+    conn: &mut Connection<'a>,
+    blog: &str,
+    boosty_id: i64,
+) -> QueryResult<LinkChange> {
+    link_changes::table
+        .find((blog, boosty_id))
+        .first(conn)
+        .await
+}
@@ -0,0 +1,54 @@
+use chrono::NaiveDateTime;
+use diesel::{delete, insert_into, prelude::*};
+use diesel_async::RunQueryDsl;
+
+use crate::{
+    models::{LinkEvent, NewLinkEvent},
+    schema::link_events,
+};
+
+use super::Connection;
+
+pub async fn create_link_event<'a>(
+    conn: &mut Connection<'a>,
+    event: NewLinkEvent<'_>,
+) -> QueryResult<LinkEvent> {
+    insert_into(link_events::table)
+        .values(event)
+        .returning(LinkEvent::as_returning())
+        .get_result(conn)
+        .await
+}
+
+pub async fn get_link_event_users<'a>(
+    conn: &mut Connection<'a>,
+    blog: &str,
+    boosty_id: i64,
+    kinds: &[&str],
+    since: NaiveDateTime,
+) -> QueryResult<Vec<i64>> {
+    link_events::table
+        .filter(link_events::blog.eq(blog))
+        .filter(link_events::boosty_id.eq(boosty_id))
+        .filter(link_events::kind.eq_any(kinds))
+        .filter(link_events::created_at.ge(since))
+        .select(link_events::user_id)
+        .distinct()
+        .order(link_events::user_id)
+        .load(conn)
+        .await
+}
+
+pub async fn remove_link_events<'a>(
+    conn: &mut Connection<'a>,
+    blog: &str,
+    boosty_id: i64,
+) -> QueryResult<usize> {
+    delete(
+        link_events::table
+            .filter(link_events::blog.eq(blog))
+            .filter(link_events::boosty_id.eq(boosty_id)),
+    )
+    .execute(conn)
+    .await
+}
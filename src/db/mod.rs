@@ -1,3 +1,15 @@
+pub mod access_events;
+pub mod account_flags;
+pub mod credentials;
+pub mod decline_notices;
+pub mod deferred_join_requests;
+pub mod expiry_history;
+pub mod invite_links;
+pub mod link_changes;
+pub mod link_events;
+pub mod pending_links;
+pub mod sent_reminders;
+pub mod user_settings;
 pub mod users;
 
 use diesel_async::{pooled_connection::AsyncDieselConnectionManager, AsyncPgConnection};
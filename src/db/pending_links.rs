@@ -0,0 +1,68 @@
+use chrono::NaiveDateTime;
+use diesel::{delete, insert_into, prelude::*, upsert::excluded};
+use diesel_async::RunQueryDsl;
+
+use crate::{models::PendingLink, schema::pending_links};
+
+use super::Connection;
+
+pub async fn upsert_pending_link<'a>(
+    conn: &mut Connection<'a>,
+    pending_link: PendingLink,
+) -> QueryResult<PendingLink> {
+    insert_into(pending_links::table)
+        .values(pending_link)
+        .on_conflict(pending_links::id)
+        .do_update()
+        .set((
+            pending_links::boosty_id.eq(excluded(pending_links::boosty_id)),
+            pending_links::blog.eq(excluded(pending_links::blog)),
+            pending_links::email.eq(excluded(pending_links::email)),
+            pending_links::code.eq(excluded(pending_links::code)),
+            pending_links::expires_at.eq(excluded(pending_links::expires_at)),
+            pending_links::sent_at.eq(excluded(pending_links::sent_at)),
+        ))
+        .returning(PendingLink::as_returning())
+        .get_result(conn)
+        .await
+}
+
+pub async fn get_pending_link<'a>(conn: &mut Connection<'a>, id: i64) -> QueryResult<PendingLink> {
+    pending_links::table.find(id).first(conn).await
+}
+
+pub async fn get_last_pending_link_for_email<'a>(
+    conn: &mut Connection<'a>,
+    email: &str,
+    sent_after: NaiveDateTime,
+) -> QueryResult<Option<PendingLink>> {
+    pending_links::table
+        .filter(pending_links::email.eq(email))
+        .filter(pending_links::sent_at.gt(sent_after))
+        .order(pending_links::sent_at.desc())
+        .first(conn)
+        .await
+        .optional()
+}
+
+pub async fn increment_pending_link_attempts<'a>(
+    conn: &mut Connection<'a>,
+    id: i64,
+) -> QueryResult<PendingLink> {
+    diesel::update(pending_links::table.find(id))
+        .set(pending_links::attempts.eq(pending_links::attempts + 1))
+        .returning(PendingLink::as_returning())
+        .get_result(conn)
+        .await
+}
+
+pub async fn remove_pending_link<'a>(conn: &mut Connection<'a>, id: i64) -> QueryResult<usize> {
+    delete(pending_links::table)
+        .filter(pending_links::id.eq(id))
+        .execute(conn)
+        .await
+}
+
+pub async fn count_pending_links<'a>(conn: &mut Connection<'a>) -> QueryResult<i64> {
+    pending_links::table.count().get_result(conn).await
+}
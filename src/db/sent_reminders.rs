@@ -0,0 +1,40 @@
+use diesel::{delete, dsl::exists, insert_into, prelude::*, select};
+use diesel_async::RunQueryDsl;
+
+use crate::{models::SentReminder, schema::sent_reminders};
+
+use super::Connection;
+
+pub async fn is_reminder_sent<'a>(
+    conn: &mut Connection<'a>,
+    reminder: &SentReminder,
+) -> QueryResult<bool> {
+    select(exists(sent_reminders::table.find((
+        reminder.user_id,
+        reminder.expires_at,
+        reminder.days,
+    ))))
+    .get_result(conn)
+    .await
+}
+
+pub async fn create_sent_reminder<'a>(
+    conn: &mut Connection<'a>,
+    reminder: SentReminder,
+) -> QueryResult<usize> {
+    insert_into(sent_reminders::table)
+        .values(reminder)
+        .on_conflict_do_nothing()
+        .execute(conn)
+        .await
+}
+
+pub async fn remove_sent_reminders<'a>(
+    conn: &mut Connection<'a>,
+    user_id: i64,
+) -> QueryResult<usize> {
+    delete(sent_reminders::table)
+        .filter(sent_reminders::user_id.eq(user_id))
+        .execute(conn)
+        .await
+}
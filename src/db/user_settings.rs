@@ -0,0 +1,47 @@
+use diesel::{insert_into, prelude::*};
+use diesel_async::RunQueryDsl;
+
+use crate::{models::UserSettings, schema::user_settings};
+
+use super::Connection;
+
+pub async fn get_user_settings<'a>(
+    conn: &mut Connection<'a>,
+    user_id: i64,
+) -> QueryResult<UserSettings> {
+    user_settings::table.find(user_id).first(conn).await
+}
+
+pub async fn upsert_user_locale<'a>(
+    conn: &mut Connection<'a>,
+    user_id: i64,
+    locale: &str,
+) -> QueryResult<usize> {
+    insert_into(user_settings::table)
+        .values((
+            user_settings::user_id.eq(user_id),
+            user_settings::locale.eq(locale),
+        ))
+        .on_conflict(user_settings::user_id)
+        .do_update()
+        .set(user_settings::locale.eq(locale))
+        .execute(conn)
+        .await
+}
+
+pub async fn upsert_user_language_code<'a>(
+    conn: &mut Connection<'a>,
+    user_id: i64,
+    language_code: &str,
+) -> QueryResult<usize> {
+    insert_into(user_settings::table)
+        .values((
+            user_settings::user_id.eq(user_id),
+            user_settings::language_code.eq(language_code),
+        ))
+        .on_conflict(user_settings::user_id)
+        .do_update()
+        .set(user_settings::language_code.eq(language_code))
+        .execute(conn)
+        .await
+}
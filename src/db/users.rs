@@ -1,3 +1,4 @@
+use chrono::NaiveDateTime;
 use diesel::{delete, prelude::*};
 use diesel_async::RunQueryDsl;
 
@@ -8,6 +9,7 @@ use super::Connection;
 pub async fn update_user<'a>(conn: &mut Connection<'a>, user: User) -> QueryResult<User> {
     diesel::update(users::table)
         .filter(users::id.eq(user.id))
+        .filter(users::blog.eq(user.blog.clone()))
         .set(user)
         .get_result(conn)
         .await
@@ -21,31 +23,120 @@ pub async fn create_user<'a>(conn: &mut Connection<'a>, user: User) -> QueryResu
         .await
 }
 
-pub async fn get_user<'a>(conn: &mut Connection<'a>, id: i64) -> QueryResult<User> {
-    users::table.find(id).first(conn).await
+pub async fn get_user<'a>(conn: &mut Connection<'a>, id: i64, blog: &str) -> QueryResult<User> {
+    users::table.find((id, blog)).first(conn).await
+}
+
+/// Returns every blog link of the Telegram user.
+pub async fn get_users_by_id<'a>(conn: &mut Connection<'a>, id: i64) -> QueryResult<Vec<User>> {
+    users::table
+        .filter(users::id.eq(id))
+        .order(users::blog)
+        .load::<User>(conn)
+        .await
 }
 
 pub async fn get_user_by_boosty_id<'a>(
     conn: &mut Connection<'a>,
+    blog: &str,
     boosty_id: i64,
 ) -> QueryResult<User> {
     users::table
+        .filter(users::blog.eq(blog))
         .filter(users::boosty_id.eq(boosty_id))
         .first(conn)
         .await
 }
 
-pub async fn remove_user<'a>(conn: &mut Connection<'a>, user_id: i64) -> QueryResult<usize> {
-    delete(users::table)
-        .filter(users::id.eq(user_id))
+pub async fn get_users_by_boosty_id<'a>(
+    conn: &mut Connection<'a>,
+    boosty_id: i64,
+) -> QueryResult<Vec<User>> {
+    users::table
+        .filter(users::boosty_id.eq(boosty_id))
+        .load::<User>(conn)
+        .await
+}
+
+pub async fn count_users<'a>(conn: &mut Connection<'a>) -> QueryResult<i64> {
+    users::table.count().get_result(conn).await
+}
+
+pub async fn count_lapsed_users<'a>(
+    conn: &mut Connection<'a>,
+    suspended: bool,
+) -> QueryResult<i64> {
+    users::table
+        .filter(users::lapsed_at.is_not_null())
+        .filter(users::suspended.eq(suspended))
+        .count()
+        .get_result(conn)
+        .await
+}
+
+pub async fn remove_user<'a>(
+    conn: &mut Connection<'a>,
+    user_id: i64,
+    blog: &str,
+) -> QueryResult<usize> {
+    delete(users::table.find((user_id, blog)))
         .execute(conn)
         .await
 }
 
+pub async fn get_users_expiring_between<'a>(
+    conn: &mut Connection<'a>,
+    from: NaiveDateTime,
+    until: NaiveDateTime,
+) -> QueryResult<Vec<User>> {
+    users::table
+        .filter(users::expires_at.gt(from))
+        .filter(users::expires_at.le(until))
+        .load::<User>(conn)
+        .await
+}
+
 // pub async fn get_users<'a>(conn: &mut Connection<'a>) -> QueryResult<Vec<User>> {
 //     users::table.load::<User>(conn).await
 // }
 
-pub async fn get_users_boosty_ids<'a>(conn: &mut Connection<'a>) -> QueryResult<Vec<i64>> {
-    users::table.select(users::id).load::<i64>(conn).await
+pub async fn get_users_boosty_ids<'a>(
+    conn: &mut Connection<'a>,
+    blog: &str,
+) -> QueryResult<Vec<i64>> {
+    users::table
+        .filter(users::blog.eq(blog))
+        .select(users::boosty_id)
+        .load::<i64>(conn)
+        .await
+}
+
+pub async fn update_user_expires_at<'a>(
+    conn: &mut Connection<'a>,
+    user_id: i64,
+    blog: &str,
+    expires_at: NaiveDateTime,
+) -> QueryResult<User> {
+    diesel::update(users::table.find((user_id, blog)))
+        .set(users::expires_at.eq(expires_at))
+        .returning(User::as_returning())
+        .get_result(conn)
+        .await
+}
+
+pub async fn update_user_lapse<'a>(
+    conn: &mut Connection<'a>,
+    user_id: i64,
+    blog: &str,
+    lapsed_at: Option<NaiveDateTime>,
+    suspended: bool,
+) -> QueryResult<User> {
+    diesel::update(users::table.find((user_id, blog)))
+        .set((
+            users::lapsed_at.eq(lapsed_at),
+            users::suspended.eq(suspended),
+        ))
+        .returning(User::as_returning())
+        .get_result(conn)
+        .await
 }
@@ -1,140 +1,891 @@
+mod notifications;
+
+use std::{collections::BTreeSet, fmt::Display, sync::Arc};
+
+use chrono::{Duration, Utc};
+use diesel::QueryResult;
+use futures::TryStreamExt;
 use teloxide::{
     requests::{Requester, ResponseResult},
     types::{ChatId, ChatJoinRequest, UserId},
 };
 
 use crate::{
+    audit::{record_access, AccessDecision, Actor, Decision, Reason},
     boosty_api::{
-        types::subscribers::{Order, SortBy, SubscribersRequest},
-        BoostyClient,
+        types::subscribers::{Order, SortBy, Subscriber, SubscribersRequest},
+        BoostyClient, RequestResult,
     },
     db::{
-        users::{get_user, get_user_by_boosty_id, get_users_boosty_ids, remove_user},
-        Pool,
+        deferred_join_requests::{
+            create_deferred_join_request, get_deferred_join_requests,
+            increment_deferred_join_request_attempts, remove_deferred_join_request,
+        },
+        expiry_history::create_expiry_change,
+        invite_links::get_invite_link_by_url,
+        sent_reminders::remove_sent_reminders,
+        user_settings::upsert_user_language_code,
+        users::{
+            get_user_by_boosty_id, get_users_boosty_ids, get_users_by_id, remove_user,
+            update_user_expires_at, update_user_lapse,
+        },
+        Connection, Pool,
     },
+    invite_links::{format_invite_links, issue_invite_links, revoke_invite_link},
+    messages::Text,
+    models::{DeferredJoinRequest, NewExpiryChange, User},
+    sharing::{is_blocked, record_event, record_unlink, LinkEventKind},
+    translations::Translations,
     utils::{Bot, Config},
 };
 
+pub use notifications::{expiry_reminders_sender, notify_user};
+
+use self::notifications::notify_declined;
+
+const SUBSCRIBERS_PAGE_SIZE: u32 = 100;
+const SUBSCRIBERS_CONCURRENCY: usize = 4;
+
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct ReconciliationReport {
+    pub checked: usize,
+    pub unpaid: usize,
+    pub not_found: usize,
+    pub kicked: usize,
+    pub refreshed: usize,
+    pub in_grace: usize,
+    pub suspended: usize,
+    pub removed: usize,
+    pub restored: usize,
+}
+
+impl Display for ReconciliationReport {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "checked {}, unpaid {}, not found {}, kicked {}, refreshed {}, in grace {}, \
+            suspended {}, removed {}, restored {}",
+            self.checked,
+            self.unpaid,
+            self.not_found,
+            self.kicked,
+            self.refreshed,
+            self.in_grace,
+            self.suspended,
+            self.removed,
+            self.restored
+        )
+    }
+}
+
+async fn remove_chat_member(bot: &Bot, chat_id: ChatId, user_id: UserId) -> ResponseResult<bool> {
+    let Ok(chat_member) = bot.get_chat_member(chat_id, user_id).await else {
+        return Ok(false);
+    };
+
+    if chat_member.is_administrator() || !chat_member.is_present() {
+        return Ok(false);
+    }
+
+    bot.kick_chat_member(chat_id, user_id).await?;
+    bot.unban_chat_member(chat_id, user_id).await?;
+
+    Ok(true)
+}
+
+/// Returns the user's blog links that may grant access to `chat_id`.
+async fn chat_links(
+    conn: &mut Connection<'_>,
+    config: &Config,
+    user_id: i64,
+    chat_id: i64,
+) -> Vec<User> {
+    get_users_by_id(conn, user_id)
+        .await
+        .unwrap_or_default()
+        .into_iter()
+        .filter(|user| config.access.blog_chats(&user.blog).contains(&chat_id))
+        .collect()
+}
+
+/// Checks whether the user behind `access` may join `chat_id` through any of
+/// their blog links, filling in the Boosty account the decision is based on.
+/// Safe to repeat for deferred requests. Boosty errors are returned so callers
+/// can tell an outage from a failure.
+async fn decide_join_request(
+    boosty_client: &BoostyClient,
+    conn: &mut Connection<'_>,
+    config: &Config,
+    chat_id: i64,
+    access: &mut AccessDecision,
+    blog: &mut Option<String>,
+) -> RequestResult<Reason> {
+    let mut reason = Reason::NotLinked;
+
+    for user in chat_links(conn, config, access.user_id, chat_id).await {
+        access.boosty_id = Some(user.boosty_id);
+        *blog = Some(user.blog.clone());
+
+        reason = decide_link(boosty_client, conn, config, chat_id, &user).await?;
+
+        if reason == Reason::Approved {
+            break;
+        }
+    }
+
+    Ok(reason)
+}
+
+async fn decide_link(
+    boosty_client: &BoostyClient,
+    conn: &mut Connection<'_>,
+    config: &Config,
+    chat_id: i64,
+    user: &User,
+) -> RequestResult<Reason> {
+    if is_blocked(conn, &user.blog, user.boosty_id).await {
+        return Ok(Reason::Shared);
+    }
+
+    let boosty_users = boosty_client
+        .subscribers(
+            &user.blog,
+            &SubscribersRequest {
+                user_ids: vec![user.boosty_id as u64].into(),
+                sort_by: SortBy::default(),
+                limit: 11,
+                offset: None,
+                order: Order::default(),
+            },
+        )
+        .await?
+        .data;
+
+    let Some(boosty_user) = boosty_users.last() else {
+        return Ok(Reason::NotFound);
+    };
+
+    if boosty_user.is_black_listed {
+        return Ok(Reason::Blacklisted);
+    }
+
+    if !boosty_user.is_paid() {
+        return Ok(Reason::Unpaid);
+    }
+
+    if !config.access.grants(&user.blog, boosty_user, chat_id) {
+        return Ok(Reason::LevelMismatch);
+    }
+
+    Ok(Reason::Approved)
+}
+
+/// Approves or declines the join request according to `access.reason`,
+/// explaining declines to the user.
+#[allow(clippy::too_many_arguments)]
+async fn resolve_join_request(
+    translations: &Translations,
+    bot: &Bot,
+    conn: &mut Connection<'_>,
+    config: &Config,
+    chat_id: ChatId,
+    user_chat_id: ChatId,
+    mut access: AccessDecision,
+    blog: Option<&str>,
+) -> ResponseResult<()> {
+    let user_id = UserId(access.user_id as u64);
+
+    if access.reason == Reason::Approved {
+        bot.approve_chat_join_request(chat_id, user_id).await?;
+        access.decision = Decision::Approved;
+    } else {
+        bot.decline_chat_join_request(chat_id, user_id).await?;
+        access.decision = Decision::Declined;
+
+        notify_declined(translations, bot, conn, config, user_chat_id, &access, blog).await;
+    }
+
+    record_access(conn, access).await;
+
+    Ok(())
+}
+
 pub async fn chat_join_handler(
+    translations: Arc<Translations>,
     boosty_client: BoostyClient,
     pool: Pool,
-    config: Config,
+    config: Arc<Config>,
     bot: Bot,
     chat_join_request: ChatJoinRequest,
 ) -> ResponseResult<()> {
     let chat_id = chat_join_request.chat.id;
 
-    if chat_id.0 != config.chat_id {
+    if !config.access.manages(chat_id.0) {
         return Ok(());
     }
 
-    let from_user_id = chat_join_request.from.id;
+    let mut conn = match pool.get().await {
+        Ok(conn) => conn,
+        Err(err) => {
+            error!(
+                "Unable to get a connection, declining {}: {}",
+                chat_join_request.from.id, err
+            );
 
-    let mut conn = pool.get().await.unwrap();
+            bot.decline_chat_join_request(chat_id, chat_join_request.from.id)
+                .await?;
 
-    let decline = || async { bot.decline_chat_join_request(chat_id, from_user_id).await };
+            return Ok(());
+        }
+    };
+
+    // Remembered so that deferred decisions are explained in the user's language.
+    if let Some(language_code) = &chat_join_request.from.language_code {
+        if let Err(err) =
+            upsert_user_language_code(&mut conn, chat_join_request.from.id.0 as i64, language_code)
+                .await
+        {
+            warn!(
+                "Unable to save language of {}: {}",
+                chat_join_request.from.id, err
+            );
+        }
+    }
 
-    let user_result = get_user(&mut conn, from_user_id.0 as i64).await;
+    let mut access = AccessDecision {
+        actor: Actor::JoinRequest,
+        user_id: chat_join_request.from.id.0 as i64,
+        boosty_id: None,
+        chat_id: Some(chat_id.0),
+        decision: Decision::Declined,
+        reason: Reason::NotLinked,
+    };
+    let mut blog = None;
+
+    // Personal links create join requests, so one forwarded to someone else
+    // ends up here instead of letting them in.
+    if let Some(invite_link) = &chat_join_request.invite_link {
+        let owner_id = get_invite_link_by_url(&mut conn, chat_id.0, &invite_link.invite_link)
+            .await
+            .ok()
+            .map(|invite_link| invite_link.user_id)
+            .filter(|owner_id| *owner_id != access.user_id);
+
+        if let Some(owner_id) = owner_id {
+            access.reason = Reason::ForeignLink;
+
+            // Someone else joining with the owner's link is another user of their account.
+            let owner = chat_links(&mut conn, &config, owner_id, chat_id.0)
+                .await
+                .into_iter()
+                .next();
+
+            if let Some(owner) = owner {
+                access.boosty_id = Some(owner.boosty_id);
+
+                record_event(
+                    &translations,
+                    &bot,
+                    &mut conn,
+                    &config,
+                    &owner.blog,
+                    owner.boosty_id,
+                    access.user_id,
+                    LinkEventKind::Join,
+                )
+                .await;
+            }
 
-    if user_result.is_err() {
-        decline().await?;
+            return resolve_join_request(
+                &translations,
+                &bot,
+                &mut conn,
+                &config,
+                chat_id,
+                chat_join_request.user_chat_id,
+                access,
+                None,
+            )
+            .await;
+        }
+    }
 
-        return Ok(());
+    // Recorded here rather than in `decide_join_request` so retries don't count.
+    for user in chat_links(&mut conn, &config, access.user_id, chat_id.0).await {
+        record_event(
+            &translations,
+            &bot,
+            &mut conn,
+            &config,
+            &user.blog,
+            user.boosty_id,
+            user.id,
+            LinkEventKind::Join,
+        )
+        .await;
     }
 
-    let user = user_result.unwrap();
+    let res = decide_join_request(
+        &boosty_client,
+        &mut conn,
+        &config,
+        chat_id.0,
+        &mut access,
+        &mut blog,
+    )
+    .await;
+
+    access.reason = match res {
+        Ok(reason) => reason,
+        Err(err) if !err.is_transient() => {
+            if err.is_auth() {
+                error!(
+                    "Boosty rejected bot credentials, declining {}: {}",
+                    access.user_id, err
+                );
+            } else {
+                error!(
+                    "Unable to check {} on Boosty, declining: {}",
+                    access.user_id, err
+                );
+            }
+
+            Reason::ApiError
+        }
+        Err(err) => {
+            warn!(
+                "Boosty is unavailable, deferring {}: {}",
+                access.user_id, err
+            );
+
+            let res = create_deferred_join_request(
+                &mut conn,
+                DeferredJoinRequest {
+                    chat_id: chat_id.0,
+                    user_id: access.user_id,
+                    user_chat_id: chat_join_request.user_chat_id.0,
+                    requested_at: Utc::now().naive_utc(),
+                    attempts: 0,
+                },
+            )
+            .await;
+
+            // Nothing would ever retry the request, so it's declined right away.
+            if let Err(err) = res {
+                error!(
+                    "Unable to defer join request of {}, declining: {}",
+                    access.user_id, err
+                );
+
+                Reason::ApiError
+            } else {
+                access.decision = Decision::Deferred;
+                access.reason = Reason::ApiError;
+                record_access(&mut conn, access).await;
+
+                return Ok(());
+            }
+        }
+    };
+
+    resolve_join_request(
+        &translations,
+        &bot,
+        &mut conn,
+        &config,
+        chat_id,
+        chat_join_request.user_chat_id,
+        access,
+        blog.as_deref(),
+    )
+    .await
+}
+
+/// Retries join requests deferred during a Boosty outage, declining those
+/// older than `join_request_max_age`.
+pub async fn deferred_join_requests_retrier(
+    translations: Arc<Translations>,
+    boosty_client: BoostyClient,
+    pool: Pool,
+    config: Arc<Config>,
+    bot: Bot,
+) -> ResponseResult<()> {
+    let mut conn = match pool.get().await {
+        Ok(conn) => conn,
+        Err(err) => {
+            error!("Unable to get a connection to retry join requests: {}", err);
+            return Ok(());
+        }
+    };
+
+    let expire_before =
+        Utc::now().naive_utc() - Duration::from_std(config.join_request_max_age).unwrap();
 
-    let res = boosty_client
-        .subscribers(&SubscribersRequest {
-            user_ids: vec![user.boosty_id as u64].into(),
-            sort_by: SortBy::default(),
-            limit: 11,
-            offset: None,
-            order: Order::default(),
-        })
+    let requests = match get_deferred_join_requests(&mut conn).await {
+        Ok(requests) => requests,
+        Err(err) => {
+            error!("Unable to get deferred join requests: {}", err);
+            return Ok(());
+        }
+    };
+
+    for request in requests {
+        let mut access = AccessDecision {
+            actor: Actor::JoinRequest,
+            user_id: request.user_id,
+            boosty_id: None,
+            chat_id: Some(request.chat_id),
+            decision: Decision::Declined,
+            reason: Reason::NotLinked,
+        };
+        let mut blog = None;
+
+        let res = decide_join_request(
+            &boosty_client,
+            &mut conn,
+            &config,
+            request.chat_id,
+            &mut access,
+            &mut blog,
+        )
         .await;
 
-    if res.is_err() {
-        decline().await?;
+        access.reason = match res {
+            Ok(reason) => reason,
+            Err(err) if err.is_transient() && request.requested_at > expire_before => {
+                if let Err(err) = increment_deferred_join_request_attempts(
+                    &mut conn,
+                    request.chat_id,
+                    request.user_id,
+                )
+                .await
+                {
+                    warn!(
+                        "Unable to count attempt for join request of {} to chat {}: {}",
+                        request.user_id, request.chat_id, err
+                    );
+                }
 
-        return Ok(());
+                continue;
+            }
+            Err(err) => {
+                error!(
+                    "Unable to check deferred join request of {} to chat {} on Boosty, declining: {}",
+                    request.user_id, request.chat_id, err
+                );
+
+                Reason::ApiError
+            }
+        };
+
+        // Left for the next round rather than risking a second resolution.
+        if let Err(err) =
+            remove_deferred_join_request(&mut conn, request.chat_id, request.user_id).await
+        {
+            error!(
+                "Unable to remove deferred join request of {} to chat {}: {}",
+                request.user_id, request.chat_id, err
+            );
+            continue;
+        }
+
+        let res = resolve_join_request(
+            &translations,
+            &bot,
+            &mut conn,
+            &config,
+            ChatId(request.chat_id),
+            ChatId(request.user_chat_id),
+            access,
+            blog.as_deref(),
+        )
+        .await;
+
+        if let Err(err) = res {
+            warn!(
+                "Unable to resolve deferred join request of {} to chat {} after {} attempts: {}",
+                request.user_id, request.chat_id, request.attempts, err
+            );
+        }
     }
 
-    let boosty_users = res.unwrap().data;
+    Ok(())
+}
+
+/// Applies the access policy to a single linked user according to their current
+/// Boosty subscription. A subscriber Boosty no longer returns (`None`) goes
+/// through the same grace, suspension and removal as an unpaid one. Stops at
+/// the first failed database write, leaving the rest for the next check.
+#[allow(clippy::too_many_arguments)]
+pub async fn reconcile_user(
+    translations: &Translations,
+    bot: &Bot,
+    conn: &mut Connection<'_>,
+    config: &Config,
+    actor: Actor,
+    user: User,
+    boosty_user: Option<&Subscriber>,
+    report: &mut ReconciliationReport,
+) -> QueryResult<()> {
+    report.checked += 1;
+
+    let paid_user = boosty_user.filter(|boosty_user| boosty_user.is_paid());
+    let black_listed = boosty_user.is_some_and(|boosty_user| boosty_user.is_black_listed);
+
+    let mut access = AccessDecision {
+        actor,
+        user_id: user.id,
+        boosty_id: Some(user.boosty_id),
+        chat_id: None,
+        decision: Decision::Kicked,
+        reason: if boosty_user.is_none() {
+            Reason::NotFound
+        } else if black_listed {
+            Reason::Blacklisted
+        } else if paid_user.is_some() {
+            Reason::LevelMismatch
+        } else {
+            Reason::Unpaid
+        },
+    };
+
+    if let Some(boosty_user) = paid_user {
+        if user.lapsed_at.is_some() {
+            update_user_lapse(conn, user.id, &user.blog, None, false).await?;
+
+            if user.suspended {
+                let granted_chats = config.access.granted_chats(&user.blog, boosty_user);
+                let invite_links = format_invite_links(
+                    issue_invite_links(bot, conn, config, user.id, &granted_chats).await,
+                );
+                notify_user(
+                    translations,
+                    bot,
+                    conn,
+                    user.id,
+                    Text::AccessRestored { invite_links },
+                )
+                .await;
+                record_access(
+                    conn,
+                    AccessDecision {
+                        decision: Decision::Restored,
+                        reason: Reason::Renewed,
+                        ..access
+                    },
+                )
+                .await;
+
+                report.restored += 1;
+            }
+        }
 
-    if boosty_users.is_empty() {
-        decline().await?;
+        if boosty_user.expires_at() != user.expires_at {
+            let new_expires_at = boosty_user.expires_at();
+
+            update_user_expires_at(conn, user.id, &user.blog, new_expires_at).await?;
+            create_expiry_change(
+                conn,
+                NewExpiryChange {
+                    user_id: user.id,
+                    old_expires_at: Some(user.expires_at),
+                    new_expires_at,
+                },
+            )
+            .await?;
+
+            report.refreshed += 1;
+        }
+    } else {
+        // Not-found subscribers are already counted as such by the checker.
+        if boosty_user.is_some() {
+            report.unpaid += 1;
+        }
 
-        return Ok(());
+        let now = Utc::now().naive_utc();
+        let lapsed_at = user.lapsed_at.unwrap_or(now);
+        let suspend_at = lapsed_at + Duration::from_std(config.grace_period).unwrap();
+        let remove_at = suspend_at + Duration::from_std(config.suspension_period).unwrap();
+
+        // Blacklisted subscribers were banned by the author, not late with a payment.
+        if black_listed || now >= remove_at {
+            remove_user(conn, user.id, &user.blog).await?;
+
+            if let Err(err) = remove_sent_reminders(conn, user.id).await {
+                warn!("Unable to remove sent reminders of {}: {}", user.id, err);
+            }
+
+            record_unlink(conn, &user.blog, user.boosty_id, user.id).await;
+
+            notify_user(translations, bot, conn, user.id, Text::AccessRevoked).await;
+            record_access(
+                conn,
+                AccessDecision {
+                    decision: Decision::Removed,
+                    ..access
+                },
+            )
+            .await;
+
+            report.removed += 1;
+        } else if now >= suspend_at {
+            if !user.suspended {
+                update_user_lapse(conn, user.id, &user.blog, Some(lapsed_at), true).await?;
+
+                notify_user(
+                    translations,
+                    bot,
+                    conn,
+                    user.id,
+                    Text::AccessSuspended {
+                        remove_at: remove_at.format("%Y-%m-%d %H:%M:%S").to_string(),
+                    },
+                )
+                .await;
+                record_access(
+                    conn,
+                    AccessDecision {
+                        decision: Decision::Suspended,
+                        ..access
+                    },
+                )
+                .await;
+            }
+
+            report.suspended += 1;
+        } else {
+            if user.lapsed_at.is_none() {
+                update_user_lapse(conn, user.id, &user.blog, Some(lapsed_at), false).await?;
+
+                notify_user(
+                    translations,
+                    bot,
+                    conn,
+                    user.id,
+                    Text::AccessGrace {
+                        suspend_at: suspend_at.format("%Y-%m-%d %H:%M:%S").to_string(),
+                    },
+                )
+                .await;
+            }
+
+            report.in_grace += 1;
+
+            return Ok(());
+        }
     }
 
-    if !boosty_users.last().unwrap().is_paid() {
-        decline().await?;
+    let granted_chats = paid_user
+        .map(|boosty_user| config.access.granted_chats(&user.blog, boosty_user))
+        .unwrap_or_default();
+    let mut revoked_chats: BTreeSet<i64> = config
+        .access
+        .blog_chats(&user.blog)
+        .difference(&granted_chats)
+        .copied()
+        .collect();
 
-        return Ok(());
+    // Chats shared with the user's other blogs are left to those links.
+    for other in get_users_by_id(conn, user.id).await? {
+        if other.blog != user.blog && !other.suspended {
+            revoked_chats
+                .retain(|chat_id| !config.access.blog_chats(&other.blog).contains(chat_id));
+        }
     }
 
-    bot.approve_chat_join_request(chat_id, from_user_id).await?;
+    access.decision = Decision::Kicked;
+    report.kicked += remove_from_chats(bot, conn, access, &revoked_chats).await;
 
     Ok(())
 }
 
+/// Revokes invite links and removes the user from `chats`, recording every kick
+/// as `access` and returning how many chats they were actually kicked from.
+pub async fn remove_from_chats(
+    bot: &Bot,
+    conn: &mut Connection<'_>,
+    access: AccessDecision,
+    chats: &BTreeSet<i64>,
+) -> usize {
+    let user_id = access.user_id;
+    let mut kicked = 0;
+
+    for chat_id in chats {
+        revoke_invite_link(bot, conn, user_id, *chat_id).await;
+
+        match remove_chat_member(bot, ChatId(*chat_id), UserId(user_id as u64)).await {
+            Ok(true) => {
+                record_access(
+                    conn,
+                    AccessDecision {
+                        chat_id: Some(*chat_id),
+                        ..access
+                    },
+                )
+                .await;
+
+                kicked += 1;
+            }
+            Ok(false) => {}
+            Err(err) => error!(
+                "Unable to remove {} from chat {}: {}",
+                user_id, chat_id, err
+            ),
+        }
+    }
+
+    kicked
+}
+
 pub async fn chat_subscribers_checker(
+    translations: Arc<Translations>,
     boosty_client: BoostyClient,
     pool: Pool,
-    config: Config,
+    config: Arc<Config>,
     bot: Bot,
-) -> ResponseResult<()> {
-    let mut conn = pool.get().await.unwrap();
-
-    let boosty_ids: Vec<u64> = get_users_boosty_ids(&mut conn)
-        .await
-        .unwrap()
-        .iter()
-        .map(|value| *value as _)
-        .collect();
-
-    let boosty_users = boosty_client
-        .subscribers(&SubscribersRequest {
-            user_ids: boosty_ids.into(),
-            sort_by: SortBy::default(),
-            limit: 100,
-            offset: None,
-            order: Order::default(),
-        })
-        .await
-        .unwrap()
-        .data;
-
-    for boosty_user in boosty_users {
-        if boosty_user.is_paid() {
-            continue;
+) -> ResponseResult<ReconciliationReport> {
+    let mut report = ReconciliationReport::default();
+    let mut conn = match pool.get().await {
+        Ok(conn) => conn,
+        Err(err) => {
+            error!("Unable to get a connection to check subscribers: {}", err);
+            return Ok(report);
         }
+    };
 
-        let db_result = get_user_by_boosty_id(&mut conn, boosty_user.basic_info.id as i64).await;
-
-        let chat_id = ChatId(config.chat_id);
-
-        if let Ok(user) = db_result {
-            remove_user(&mut conn, user.id).await.unwrap();
+    for blog in &config.blogs {
+        let boosty_ids: Vec<u64> = match get_users_boosty_ids(&mut conn, blog).await {
+            Ok(boosty_ids) => boosty_ids.iter().map(|value| *value as _).collect(),
+            Err(err) => {
+                error!("Unable to get linked subscribers of `{}`: {}", blog, err);
+                continue;
+            }
+        };
+
+        let mut boosty_users = Vec::with_capacity(boosty_ids.len());
+        let mut fetched_all = true;
+
+        for ids_chunk in boosty_ids.chunks(SUBSCRIBERS_PAGE_SIZE as usize) {
+            let page: RequestResult<Vec<Subscriber>> = boosty_client
+                .subscribers_stream(
+                    blog,
+                    SubscribersRequest {
+                        user_ids: ids_chunk.to_vec().into(),
+                        sort_by: SortBy::default(),
+                        limit: SUBSCRIBERS_PAGE_SIZE,
+                        offset: None,
+                        order: Order::default(),
+                    },
+                    SUBSCRIBERS_CONCURRENCY,
+                )
+                .try_collect()
+                .await;
+
+            match page {
+                Ok(page) => boosty_users.extend(page),
+                Err(err) => {
+                    error!("Unable to fetch subscribers of `{}`: {}", blog, err);
+                    fetched_all = false;
+                    break;
+                }
+            }
+        }
 
-            let user_id = UserId(user.id as u64);
+        // Only a complete answer tells missing subscribers from unfetched ones.
+        let not_found: Vec<u64> = if fetched_all {
+            boosty_ids
+                .iter()
+                .copied()
+                .filter(|id| {
+                    !boosty_users
+                        .iter()
+                        .any(|boosty_user| boosty_user.basic_info.id == *id)
+                })
+                .collect()
+        } else {
+            vec![]
+        };
+
+        if !not_found.is_empty() {
+            warn!(
+                "Boosty doesn't know {} of {} linked subscribers of `{}`.",
+                not_found.len(),
+                boosty_ids.len(),
+                blog
+            );
+        }
 
-            let chat_member = bot.get_chat_member(chat_id, user_id).await;
+        report.not_found += not_found.len();
 
-            if chat_member.is_err() {
+        for boosty_id in not_found {
+            let Ok(user) = get_user_by_boosty_id(&mut conn, blog, boosty_id as i64).await else {
                 continue;
+            };
+            let user_id = user.id;
+
+            let res = reconcile_user(
+                &translations,
+                &bot,
+                &mut conn,
+                &config,
+                Actor::Checker,
+                user,
+                None,
+                &mut report,
+            )
+            .await;
+
+            if let Err(err) = res {
+                error!("Unable to reconcile {}: {}", user_id, err);
             }
+        }
 
-            let chat_member = chat_member.unwrap();
+        for mut boosty_user in boosty_users {
+            let db_result =
+                get_user_by_boosty_id(&mut conn, blog, boosty_user.basic_info.id as i64).await;
 
-            if chat_member.is_administrator() || !chat_member.is_present() {
+            let Ok(user) = db_result else {
                 continue;
+            };
+            let user_id = user.id;
+
+            if boosty_user.is_paid() {
+                match boosty_client
+                    .paid_until(blog, boosty_user.basic_info.id)
+                    .await
+                {
+                    Ok(paid_until) => boosty_user.paid_until = paid_until,
+                    // Keeps the stored expiry rather than overwriting it with an estimate.
+                    Err(err) => {
+                        error!("Unable to fetch payments of {} on Boosty: {}", user_id, err);
+                        boosty_user.paid_until = Some(user.expires_at);
+                    }
+                }
             }
 
-            bot.kick_chat_member(chat_id, user_id).await.unwrap();
+            let res = reconcile_user(
+                &translations,
+                &bot,
+                &mut conn,
+                &config,
+                Actor::Checker,
+                user,
+                Some(&boosty_user),
+                &mut report,
+            )
+            .await;
+
+            if let Err(err) = res {
+                error!("Unable to reconcile {}: {}", user_id, err);
+            }
         }
     }
 
-    Ok(())
+    info!("Subscribers reconciliation: {}", report);
+    info!(
+        "Boosty rate limiter: {}",
+        boosty_client.rate_limiter_metrics()
+    );
+
+    Ok(report)
 }
@@ -0,0 +1,202 @@
+use std::sync::Arc;
+
+use chrono::{Duration, Utc};
+use teloxide::{
+    payloads::SendMessageSetters,
+    requests::{Requester, ResponseResult},
+    types::{ChatId, InlineKeyboardButton, InlineKeyboardMarkup, UserId},
+};
+
+use crate::{
+    audit::{AccessDecision, Reason},
+    commands::EMAIL_CALLBACK,
+    db::{
+        decline_notices::claim_decline_notice,
+        sent_reminders::{create_sent_reminder, is_reminder_sent},
+        users::get_users_expiring_between,
+        Connection, Pool,
+    },
+    messages::{render, Text},
+    models::{DeclineNotice, SentReminder},
+    translations::{user_bundle, Translations},
+    utils::{Bot, Config},
+};
+
+const SECONDS_IN_DAY: i64 = 24 * 60 * 60;
+
+pub async fn expiry_reminders_sender(
+    translations: Arc<Translations>,
+    pool: Pool,
+    config: Arc<Config>,
+    bot: Bot,
+) -> ResponseResult<()> {
+    let Some(max_days) = config.reminder_days.last() else {
+        return Ok(());
+    };
+
+    let mut conn = match pool.get().await {
+        Ok(conn) => conn,
+        Err(err) => {
+            error!(
+                "Unable to get a connection to send expiry reminders: {}",
+                err
+            );
+            return Ok(());
+        }
+    };
+    let now = Utc::now().naive_utc();
+
+    let users =
+        match get_users_expiring_between(&mut conn, now, now + Duration::days(*max_days as i64))
+            .await
+        {
+            Ok(users) => users,
+            Err(err) => {
+                error!("Unable to get users with expiring subscriptions: {}", err);
+                return Ok(());
+            }
+        };
+
+    for user in users {
+        let seconds_left = (user.expires_at - now).num_seconds();
+        let days_left = (seconds_left + SECONDS_IN_DAY - 1) / SECONDS_IN_DAY;
+
+        // Only the closest threshold is sent, so a restart after a long downtime
+        // doesn't deliver every missed reminder at once.
+        let Some(days) = config
+            .reminder_days
+            .iter()
+            .copied()
+            .find(|days| *days as i64 >= days_left)
+        else {
+            continue;
+        };
+
+        let reminder = SentReminder {
+            user_id: user.id,
+            expires_at: user.expires_at,
+            days,
+        };
+
+        match is_reminder_sent(&mut conn, &reminder).await {
+            Ok(false) => {}
+            Ok(true) => continue,
+            Err(err) => {
+                error!("Unable to check expiry reminders of {}: {}", user.id, err);
+                continue;
+            }
+        }
+
+        let lang_bundle = user_bundle(&translations, &mut conn, user.id, None).await;
+
+        let text = render(
+            &lang_bundle,
+            Text::ExpiryReminder {
+                days_left,
+                expires_at: user.expires_at.format("%Y-%m-%d %H:%M:%S").to_string(),
+            },
+        );
+
+        match bot.send_message(UserId(user.id as u64), text).await {
+            Ok(_) => {
+                if let Err(err) = create_sent_reminder(&mut conn, reminder).await {
+                    error!("Unable to save expiry reminder of {}: {}", user.id, err);
+                }
+            }
+            Err(err) => warn!("Unable to send expiry reminder to {}: {}", user.id, err),
+        }
+    }
+
+    Ok(())
+}
+
+pub async fn notify_user(
+    translations: &Translations,
+    bot: &Bot,
+    conn: &mut Connection<'_>,
+    user_id: i64,
+    text: Text,
+) {
+    let lang_bundle = user_bundle(translations, conn, user_id, None).await;
+    let id = text.id();
+
+    if let Err(err) = bot
+        .send_message(UserId(user_id as u64), render(&lang_bundle, text))
+        .await
+    {
+        warn!("Unable to send `{}` to {}: {}", id, user_id, err);
+    }
+}
+
+/// Tells the user why their join request was declined, at most once per
+/// `decline_notice_cooldown`.
+pub async fn notify_declined(
+    translations: &Translations,
+    bot: &Bot,
+    conn: &mut Connection<'_>,
+    config: &Config,
+    chat_id: ChatId,
+    access: &AccessDecision,
+    blog: Option<&str>,
+) {
+    let now = Utc::now().naive_utc();
+    let not_before = now - Duration::from_std(config.decline_notice_cooldown).unwrap();
+
+    let claimed = claim_decline_notice(
+        conn,
+        DeclineNotice {
+            user_id: access.user_id,
+            sent_at: now,
+        },
+        not_before,
+    )
+    .await;
+
+    match claimed {
+        Ok(true) => {}
+        Ok(false) => return,
+        Err(err) => {
+            error!(
+                "Unable to claim decline notice of {}: {}",
+                access.user_id, err
+            );
+            return;
+        }
+    }
+
+    let lang_bundle = user_bundle(translations, conn, access.user_id, None).await;
+
+    let text = render(
+        &lang_bundle,
+        Text::JoinDeclined {
+            reason: access.reason.as_str(),
+        },
+    );
+
+    let button = match (access.reason, blog) {
+        (Reason::NotLinked, _) => Some(InlineKeyboardButton::callback(
+            render(&lang_bundle, Text::JoinDeclinedButtonEmail),
+            EMAIL_CALLBACK,
+        )),
+        (Reason::NotFound | Reason::Unpaid | Reason::LevelMismatch, Some(blog)) => {
+            Some(InlineKeyboardButton::url(
+                render(&lang_bundle, Text::JoinDeclinedButtonSubscribe),
+                format!("https://boosty.to/{}", blog).parse().unwrap(),
+            ))
+        }
+        _ => None,
+    };
+
+    let mut request = bot.send_message(chat_id, text);
+
+    if let Some(button) = button {
+        request = request.reply_markup(InlineKeyboardMarkup::new([[button]]));
+    }
+
+    if let Err(err) = request.await {
+        warn!(
+            "Unable to explain declined join request to {}: {}",
+            access.user_id, err
+        );
+    }
+}
@@ -0,0 +1,113 @@
+use std::collections::BTreeSet;
+
+use chrono::Utc;
+use teloxide::{payloads::CreateChatInviteLinkSetters, requests::Requester, types::ChatId};
+
+use crate::{
+    db::{
+        invite_links::{get_invite_link, remove_invite_link, upsert_invite_link},
+        Connection,
+    },
+    models::InviteLink,
+    utils::{Bot, Config},
+};
+
+pub fn format_invite_links(invite_links: Vec<String>) -> String {
+    if invite_links.is_empty() {
+        "none".to_string()
+    } else {
+        invite_links.join("\n")
+    }
+}
+
+/// Returns a personal invite link for every chat in `chats`, reusing the one
+/// issued before until it expires. The links create join requests, which are
+/// only approved for the user the link was issued to.
+pub async fn issue_invite_links(
+    bot: &Bot,
+    conn: &mut Connection<'_>,
+    config: &Config,
+    user_id: i64,
+    chats: &BTreeSet<i64>,
+) -> Vec<String> {
+    let mut links = Vec::with_capacity(chats.len());
+
+    for chat_id in chats {
+        if let Ok(invite_link) = get_invite_link(conn, user_id, *chat_id).await {
+            if invite_link.expires_at > Utc::now().naive_utc() {
+                links.push(invite_link.invite_link);
+                continue;
+            }
+        }
+
+        revoke_invite_link(bot, conn, user_id, *chat_id).await;
+
+        let expires_at = Utc::now() + config.invite_link_ttl;
+
+        let res = bot
+            .create_chat_invite_link(ChatId(*chat_id))
+            .name(format!("user {}", user_id))
+            .creates_join_request(true)
+            .expire_date(expires_at)
+            .await;
+
+        let invite_link = match res {
+            Ok(invite_link) => invite_link.invite_link,
+            Err(err) => {
+                error!(
+                    "Unable to create invite link to chat {} for {}: {}",
+                    chat_id, user_id, err
+                );
+                continue;
+            }
+        };
+
+        let res = upsert_invite_link(
+            conn,
+            InviteLink {
+                user_id,
+                chat_id: *chat_id,
+                invite_link: invite_link.clone(),
+                expires_at: expires_at.naive_utc(),
+            },
+        )
+        .await;
+
+        // The link still works, it just won't be reused or revoked later.
+        if let Err(err) = res {
+            error!(
+                "Unable to save invite link to chat {} for {}: {}",
+                chat_id, user_id, err
+            );
+        }
+
+        links.push(invite_link);
+    }
+
+    links
+}
+
+pub async fn revoke_invite_link(bot: &Bot, conn: &mut Connection<'_>, user_id: i64, chat_id: i64) {
+    let Ok(invite_link) = get_invite_link(conn, user_id, chat_id).await else {
+        return;
+    };
+
+    if invite_link.expires_at > Utc::now().naive_utc() {
+        if let Err(err) = bot
+            .revoke_chat_invite_link(ChatId(chat_id), invite_link.invite_link)
+            .await
+        {
+            warn!(
+                "Unable to revoke invite link to chat {} for {}: {}",
+                chat_id, user_id, err
+            );
+        }
+    }
+
+    if let Err(err) = remove_invite_link(conn, user_id, chat_id).await {
+        error!(
+            "Unable to remove invite link to chat {} for {}: {}",
+            chat_id, user_id, err
+        );
+    }
+}
@@ -0,0 +1,154 @@
+use std::{
+    env,
+    fmt::{Debug, Display},
+    path::PathBuf,
+    sync::Arc,
+};
+
+use async_trait::async_trait;
+use lettre::{
+    message::Mailbox, transport::smtp::authentication::Credentials, AsyncSmtpTransport,
+    AsyncTransport, Message, Tokio1Executor,
+};
+use tokio::{fs::OpenOptions, io::AsyncWriteExt};
+
+#[derive(Debug)]
+pub enum MailerError {
+    Address(String),
+    Io(std::io::Error),
+    Transport(Box<dyn std::error::Error + Send + Sync>),
+}
+
+impl Display for MailerError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Address(err) => write!(f, "Invalid email address: {}", err),
+            Self::Io(err) => write!(f, "Unable to write the email: {}", err),
+            Self::Transport(err) => write!(f, "Unable to send the email: {}", err),
+        }
+    }
+}
+
+impl std::error::Error for MailerError {}
+
+#[async_trait]
+pub trait Mailer: Debug + Send + Sync {
+    async fn send(&self, to: &str, subject: &str, body: &str) -> Result<(), MailerError>;
+}
+
+pub struct SmtpMailer {
+    from: Mailbox,
+    transport: AsyncSmtpTransport<Tokio1Executor>,
+}
+
+impl SmtpMailer {
+    pub fn new(
+        host: &str,
+        username: String,
+        password: String,
+        from: &str,
+    ) -> Result<Self, MailerError> {
+        let transport = AsyncSmtpTransport::<Tokio1Executor>::relay(host)
+            .map_err(|err| MailerError::Transport(Box::new(err)))?
+            .credentials(Credentials::new(username, password))
+            .build();
+
+        Ok(Self {
+            from: from
+                .parse()
+                .map_err(|_| MailerError::Address(from.to_string()))?,
+            transport,
+        })
+    }
+}
+
+impl Debug for SmtpMailer {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("SmtpMailer")
+            .field("from", &self.from)
+            .finish()
+    }
+}
+
+#[async_trait]
+impl Mailer for SmtpMailer {
+    async fn send(&self, to: &str, subject: &str, body: &str) -> Result<(), MailerError> {
+        let message = Message::builder()
+            .from(self.from.clone())
+            .to(to
+                .parse()
+                .map_err(|_| MailerError::Address(to.to_string()))?)
+            .subject(subject)
+            .body(body.to_string())
+            .map_err(|err| MailerError::Transport(Box::new(err)))?;
+
+        self.transport
+            .send(message)
+            .await
+            .map_err(|err| MailerError::Transport(Box::new(err)))?;
+
+        Ok(())
+    }
+}
+
+/// Writes emails to a file (or to the log when no path is given) instead of
+/// sending them, for local testing.
+#[derive(Debug, Clone, Default)]
+pub struct FileMailer {
+    path: Option<PathBuf>,
+}
+
+impl FileMailer {
+    pub fn new(path: Option<PathBuf>) -> Self {
+        Self { path }
+    }
+}
+
+#[async_trait]
+impl Mailer for FileMailer {
+    async fn send(&self, to: &str, subject: &str, body: &str) -> Result<(), MailerError> {
+        let Some(path) = &self.path else {
+            info!("Email to {}: {}\n{}", to, subject, body);
+            return Ok(());
+        };
+
+        let mut file = OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(path)
+            .await
+            .map_err(MailerError::Io)?;
+
+        file.write_all(format!("To: {}\nSubject: {}\n\n{}\n\n", to, subject, body).as_bytes())
+            .await
+            .map_err(MailerError::Io)
+    }
+}
+
+/// Builds the mailer selected by `MAILER`. The setting is required so that a
+/// production deployment never silently writes codes to the log.
+pub fn mailer_from_env() -> Arc<dyn Mailer> {
+    match env::var("MAILER").as_deref() {
+        Ok("smtp") => {
+            let var = |name: &str| {
+                env::var(name)
+                    .unwrap_or_else(|_| panic!("Environment variable `{}` is not provided.", name))
+            };
+
+            Arc::new(
+                SmtpMailer::new(
+                    &var("SMTP_HOST"),
+                    var("SMTP_USERNAME"),
+                    var("SMTP_PASSWORD"),
+                    &var("SMTP_FROM"),
+                )
+                .unwrap_or_else(|err| panic!("Unable to configure SMTP mailer: {}", err)),
+            )
+        }
+        Ok("file") | Ok("log") => Arc::new(FileMailer::new(
+            env::var("MAILER_FILE").ok().map(PathBuf::from),
+        )),
+        Ok(other) => panic!("Unknown mailer `{}`.", other),
+        Err(_) => panic!("Environment variable `MAILER` is not provided."),
+    }
+}
@@ -1,12 +1,23 @@
+mod access;
+mod audit;
 mod boosty_api;
 mod commands;
 mod db;
 mod handlers;
+mod invite_links;
+mod mailer;
+mod messages;
 pub mod models;
 pub mod schema;
+mod sharing;
 mod translations;
 mod utils;
 
+#[cfg(test)]
+mod test_support;
+#[cfg(test)]
+mod tests;
+
 extern crate pretty_env_logger;
 #[macro_use]
 extern crate log;
@@ -20,18 +31,38 @@ use teloxide::{
     types::{ParseMode, UpdateKind},
     Bot as TeloxideBot,
 };
-use tokio::time::{sleep, Duration};
+use tokio::{
+    signal::unix::{signal, SignalKind},
+    time::{sleep, Duration},
+};
 
 use crate::{
-    boosty_api::{auth::AuthData, BoostyClient, BoostyClientBuilder},
-    commands::{handle_command, handle_unknown_command, Command},
-    db::Pool,
-    handlers::{chat_join_handler, chat_subscribers_checker},
-    translations::load_langs,
+    boosty_api::{
+        credentials::{CredentialStore, EnvCredentialStore, FileCredentialStore},
+        BoostyClient, BoostyClientBuilder, RateLimit, RetryPolicy,
+    },
+    commands::{
+        handle_admin_command, handle_command, handle_email_callback, handle_language_callback,
+        handle_unknown_command, handle_unlink_callback, AdminCommand, Command, EMAIL_CALLBACK,
+        LANGUAGE_CALLBACK_PREFIX,
+    },
+    db::{credentials::PgCredentialStore, Pool},
+    handlers::{
+        chat_join_handler, chat_subscribers_checker, deferred_join_requests_retrier,
+        expiry_reminders_sender,
+    },
+    mailer::mailer_from_env,
+    translations::Translations,
     utils::{Bot, Config},
 };
 
-async fn skip_updates(bot: &Bot, boosty_client: BoostyClient, pool: Pool, config: Config) {
+async fn skip_updates(
+    translations: Arc<Translations>,
+    bot: &Bot,
+    boosty_client: BoostyClient,
+    pool: Pool,
+    config: Arc<Config>,
+) {
     let mut last_update_id = -1;
 
     loop {
@@ -50,9 +81,10 @@ async fn skip_updates(bot: &Bot, boosty_client: BoostyClient, pool: Pool, config
         for old_update in old_updates {
             if let UpdateKind::ChatJoinRequest(chat_join_request) = &old_update.kind {
                 let _ = chat_join_handler(
+                    translations.to_owned(),
                     boosty_client.to_owned(),
                     pool.to_owned(),
-                    config,
+                    config.to_owned(),
                     bot.to_owned(),
                     chat_join_request.to_owned(),
                 )
@@ -64,6 +96,17 @@ async fn skip_updates(bot: &Bot, boosty_client: BoostyClient, pool: Pool, config
     info!("Successfully skipped old updates.");
 }
 
+fn credential_store(pool: &Pool) -> Arc<dyn CredentialStore> {
+    match env::var("CREDENTIAL_STORE").as_deref() {
+        Ok("file") | Err(_) => Arc::new(FileCredentialStore::new(
+            env::var("AUTH_FILE").unwrap_or_else(|_| "auth.toml".to_string()),
+        )),
+        Ok("env") => Arc::new(EnvCredentialStore),
+        Ok("postgres") => Arc::new(PgCredentialStore::new(pool.clone(), "default")),
+        Ok(other) => panic!("Unknown credential store `{}`.", other),
+    }
+}
+
 #[tokio::main]
 async fn main() {
     if dotenvy::dotenv().is_err() {
@@ -73,22 +116,44 @@ async fn main() {
     pretty_env_logger::init();
     info!("Starting bot...");
 
-    let lang_bundle = Arc::new(load_langs().await);
-    let boosty_client = BoostyClientBuilder::new(AuthData::load()).build();
     let database_url = env::var("DATABASE_URL").expect("DATABASE_URL must be set");
 
     let bot = TeloxideBot::from_env().parse_mode(ParseMode::Html);
 
-    let config = Config::build();
+    let config = Arc::new(Config::build());
+    let translations = Arc::new(
+        Translations::load(&config.translations_dir)
+            .unwrap_or_else(|err| panic!("Unable to load translations: {}", err)),
+    );
+    let mailer = mailer_from_env();
     let db_config =
         AsyncDieselConnectionManager::<diesel_async::AsyncPgConnection>::new(database_url);
     let pool = bb8::Pool::builder().build(db_config).await.unwrap();
 
-    skip_updates(&bot, boosty_client.clone(), pool.clone(), config).await;
+    let boosty_client = BoostyClientBuilder::from_store(credential_store(&pool))
+        .await
+        .unwrap_or_else(|err| panic!("Unable to load Boosty credentials: {}", err))
+        .with_retry_policy(RetryPolicy::from_env())
+        .with_rate_limit(RateLimit::from_env())
+        .build();
+
+    skip_updates(
+        translations.clone(),
+        &bot,
+        boosty_client.clone(),
+        pool.clone(),
+        config.clone(),
+    )
+    .await;
 
     let handler = dptree::entry()
         .branch(
             Update::filter_message()
+                .branch(
+                    dptree::entry()
+                        .filter_command::<AdminCommand>()
+                        .endpoint(handle_admin_command),
+                )
                 .branch(
                     dptree::entry()
                         .filter_command::<Command>()
@@ -96,29 +161,107 @@ async fn main() {
                 )
                 .branch(dptree::endpoint(handle_unknown_command)),
         )
+        .branch(
+            Update::filter_callback_query()
+                .branch(
+                    dptree::filter(|query: CallbackQuery| {
+                        query.data.as_deref() == Some(EMAIL_CALLBACK)
+                    })
+                    .endpoint(handle_email_callback),
+                )
+                .branch(
+                    dptree::filter(|query: CallbackQuery| {
+                        query
+                            .data
+                            .as_deref()
+                            .is_some_and(|data| data.starts_with(LANGUAGE_CALLBACK_PREFIX))
+                    })
+                    .endpoint(handle_language_callback),
+                )
+                .branch(dptree::endpoint(handle_unlink_callback)),
+        )
         .branch(Update::filter_chat_join_request().endpoint(chat_join_handler));
 
+    let translations_cloned = translations.clone();
     let bot_cloned = bot.clone();
     let boosty_cloned = boosty_client.clone();
     let pool_cloned = pool.clone();
+    let config_cloned = config.clone();
 
     tokio::spawn(async move {
         loop {
-            chat_subscribers_checker(
+            if let Err(err) = chat_subscribers_checker(
+                translations_cloned.to_owned(),
                 boosty_cloned.to_owned(),
                 pool_cloned.to_owned(),
-                config,
+                config_cloned.to_owned(),
                 bot_cloned.to_owned(),
             )
             .await
-            .unwrap();
+            {
+                error!("Unable to check subscribers: {}", err);
+            }
+
+            if let Err(err) = expiry_reminders_sender(
+                translations_cloned.to_owned(),
+                pool_cloned.to_owned(),
+                config_cloned.to_owned(),
+                bot_cloned.to_owned(),
+            )
+            .await
+            {
+                error!("Unable to send expiry reminders: {}", err);
+            }
 
             sleep(Duration::from_secs(60 * 60)).await;
         }
     });
 
+    let translations_cloned = translations.clone();
+    let bot_cloned = bot.clone();
+    let boosty_cloned = boosty_client.clone();
+    let pool_cloned = pool.clone();
+    let config_cloned = config.clone();
+
+    tokio::spawn(async move {
+        loop {
+            sleep(config_cloned.join_retry_interval).await;
+
+            if let Err(err) = deferred_join_requests_retrier(
+                translations_cloned.to_owned(),
+                boosty_cloned.to_owned(),
+                pool_cloned.to_owned(),
+                config_cloned.to_owned(),
+                bot_cloned.to_owned(),
+            )
+            .await
+            {
+                error!("Unable to retry deferred join requests: {}", err);
+            }
+        }
+    });
+
+    let translations_cloned = translations.clone();
+
+    tokio::spawn(async move {
+        let mut hangup = signal(SignalKind::hangup()).expect("Unable to listen for SIGHUP.");
+
+        while hangup.recv().await.is_some() {
+            match translations_cloned.reload() {
+                Ok(locales) => info!("Reloaded translations: {:?}", locales),
+                Err(err) => error!("Unable to reload translations: {}", err),
+            }
+        }
+    });
+
     Dispatcher::builder(bot, handler)
-        .dependencies(dptree::deps![lang_bundle, boosty_client, pool, config])
+        .dependencies(dptree::deps![
+            translations,
+            boosty_client,
+            pool,
+            config,
+            mailer
+        ])
         .default_handler(|upd| async move {
             log::warn!("Unhandled update: {:?}", upd);
         })
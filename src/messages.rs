@@ -0,0 +1,158 @@
+use fluent::FluentArgs;
+
+use crate::translations::TranslationType;
+
+macro_rules! messages {
+    ($($variant:ident $({ $($arg:ident: $ty:ty),* $(,)? })? => $id:literal,)*) => {
+        /// Every message the bot sends, with the arguments its pattern expects.
+        /// Argument names are the FTL variables with dashes replaced by underscores.
+        #[derive(Debug, Clone)]
+        pub enum Text {
+            $($variant $({ $($arg: $ty),* })?,)*
+        }
+
+        impl Text {
+            pub const IDS: &'static [&'static str] = &[$($id),*];
+
+            /// Argument names of every message, in the order of [`Self::IDS`].
+            #[cfg(test)]
+            pub const ARGS: &'static [&'static [&'static str]] =
+                &[$(&[$($(stringify!($arg)),*)?]),*];
+
+            pub fn id(&self) -> &'static str {
+                match self {
+                    $(Self::$variant { .. } => $id,)*
+                }
+            }
+
+            fn into_args(self) -> FluentArgs<'static> {
+                #[allow(unused_mut)]
+                let mut args = FluentArgs::new();
+
+                match self {
+                    $(Self::$variant $({ $($arg),* })? => {
+                        $($(args.set(stringify!($arg).replace('_', "-"), $arg);)*)?
+                    })*
+                }
+
+                args
+            }
+        }
+    };
+}
+
+messages! {
+    Start => "start",
+    Help => "help",
+    InvalidEmail => "invalid-email",
+    UserFound { email: String, name: String, level: String, invite_links: String } => "user-found",
+    UserNotSubscribed { email: String } => "user-not-subscribed",
+    UserAlreadyExists => "user-already-exists",
+    NoUserFound { email: String } => "no-user-found",
+    NoProfile => "no-profile",
+    ProfileApiError => "profile-api-error",
+    Profile {
+        name: String,
+        email: String,
+        level: String,
+        price: f32,
+        expires_at: String,
+        approximate: &'static str,
+        invite_links: String,
+    } => "profile",
+    VerificationCodeSent { email: String, minutes: u64 } => "verification-code-sent",
+    VerificationEmailError { email: String } => "verification-email-error",
+    VerificationEmailSubject => "verification-email-subject",
+    VerificationEmailBody { code: String, minutes: u64 } => "verification-email-body",
+    NoPendingVerification => "no-pending-verification",
+    VerificationExpired => "verification-expired",
+    VerificationInvalidCode { attempts_left: i32 } => "verification-invalid-code",
+    VerificationCooldown { available_at: String } => "verification-cooldown",
+    BoostyApiError => "boosty-api-error",
+    ExpiryReminder { days_left: i64, expires_at: String } => "expiry-reminder",
+    AccessRevoked => "access-revoked",
+    AccessGrace { suspend_at: String } => "access-grace",
+    AccessSuspended { remove_at: String } => "access-suspended",
+    AccessRestored { invite_links: String } => "access-restored",
+    UnlinkConfirm => "unlink-confirm",
+    UnlinkButtonConfirm => "unlink-button-confirm",
+    UnlinkButtonCancel => "unlink-button-cancel",
+    UnlinkDone => "unlink-done",
+    UnlinkCancelled => "unlink-cancelled",
+    RelinkCodeSent => "relink-code-sent",
+    RelinkCooldown { available_at: String } => "relink-cooldown",
+    AccountMoved => "account-moved",
+    AccountBlocked => "account-blocked",
+    JoinDeclined { reason: &'static str } => "join-declined",
+    JoinDeclinedButtonEmail => "join-declined-button-email",
+    JoinDeclinedButtonSubscribe => "join-declined-button-subscribe",
+    LanguageName => "language-name",
+    LanguageSelect => "language-select",
+    LanguageChanged => "language-changed",
+    AdminHelp => "admin-help",
+    AdminForbidden => "admin-forbidden",
+    AdminNotFound => "admin-not-found",
+    AdminUser {
+        tg_id: i64,
+        boosty_id: i64,
+        blog: String,
+        expires_at: String,
+        state: &'static str,
+    } => "admin-user",
+    AdminLinked { name: String, level: String } => "admin-linked",
+    AdminLinkTaken { tg_id: i64 } => "admin-link-taken",
+    AdminUnlinked { tg_id: i64 } => "admin-unlinked",
+    AdminUnblocked { blog: String, boosty_id: u64 } => "admin-unblocked",
+    SharingDetected {
+        verdict: &'static str,
+        blog: String,
+        boosty_id: i64,
+        users: usize,
+        tg_ids: String,
+    } => "sharing-detected",
+    AdminEvent {
+        created_at: String,
+        decision: String,
+        reason: String,
+        chat_id: String,
+        actor: String,
+    } => "admin-event",
+    AdminReport { report: String } => "admin-report",
+    AdminStats {
+        total: i64,
+        in_grace: i64,
+        suspended: i64,
+        pending: i64,
+        deferred: i64,
+        rate_limiter: String,
+    } => "admin-stats",
+    AdminReloaded { locales: String } => "admin-reloaded",
+    AdminReloadFailed { error: String } => "admin-reload-failed",
+}
+
+/// Formats `text` with `lang_bundle`. A message missing from the bundle is
+/// logged and rendered as its identifier rather than failing the handler.
+pub fn render(lang_bundle: &TranslationType, text: Text) -> String {
+    let id = text.id();
+
+    let Some(pattern) = lang_bundle
+        .get_message(id)
+        .and_then(|message| message.value())
+    else {
+        error!("Message with identifier `{}` doesn't exist.", id);
+        return id.to_string();
+    };
+
+    let args = text.into_args();
+    let mut errors = vec![];
+
+    let value = lang_bundle
+        .format_pattern(pattern, Some(&args), &mut errors)
+        .to_string();
+
+    if !errors.is_empty() {
+        warn!("Unable to format `{}`: {:?}", id, errors);
+    }
+
+    value
+}
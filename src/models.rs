@@ -3,11 +3,16 @@ use diesel::prelude::*;
 
 #[derive(Queryable, Selectable, Identifiable, Insertable, AsChangeset)]
 #[diesel(table_name = crate::schema::users)]
+#[diesel(primary_key(id, blog))]
 #[diesel(check_for_backend(diesel::pg::Pg))]
+#[diesel(treat_none_as_null = true)]
 pub struct User {
     pub id: i64,
     pub boosty_id: i64,
     pub expires_at: NaiveDateTime,
+    pub blog: String,
+    pub lapsed_at: Option<NaiveDateTime>,
+    pub suspended: bool,
 }
 
 // #[derive(Insertable)]
@@ -17,3 +22,160 @@ pub struct User {
 //     pub boosty_id: i64,
 //     pub expires_at: NaiveDateTime,
 // }
+
+#[derive(Queryable, Selectable, Insertable)]
+#[diesel(table_name = crate::schema::boosty_credentials)]
+#[diesel(check_for_backend(diesel::pg::Pg))]
+pub struct BoostyCredentials {
+    pub name: String,
+    pub access_token: String,
+    pub refresh_token: String,
+    pub device_id: String,
+    pub expires_at: i64,
+}
+
+#[derive(Queryable, Selectable, Insertable)]
+#[diesel(table_name = crate::schema::invite_links)]
+#[diesel(check_for_backend(diesel::pg::Pg))]
+pub struct InviteLink {
+    pub user_id: i64,
+    pub chat_id: i64,
+    pub invite_link: String,
+    pub expires_at: NaiveDateTime,
+}
+
+#[derive(Queryable, Selectable, Identifiable, Insertable, AsChangeset)]
+#[diesel(table_name = crate::schema::pending_links)]
+#[diesel(check_for_backend(diesel::pg::Pg))]
+pub struct PendingLink {
+    pub id: i64,
+    pub boosty_id: i64,
+    pub blog: String,
+    pub email: String,
+    pub code: String,
+    pub attempts: i32,
+    pub expires_at: NaiveDateTime,
+    pub sent_at: NaiveDateTime,
+}
+
+#[derive(Queryable, Selectable, Insertable)]
+#[diesel(table_name = crate::schema::sent_reminders)]
+#[diesel(check_for_backend(diesel::pg::Pg))]
+pub struct SentReminder {
+    pub user_id: i64,
+    pub expires_at: NaiveDateTime,
+    pub days: i32,
+}
+
+#[derive(Queryable, Selectable, Insertable)]
+#[diesel(table_name = crate::schema::decline_notices)]
+#[diesel(check_for_backend(diesel::pg::Pg))]
+pub struct DeclineNotice {
+    pub user_id: i64,
+    pub sent_at: NaiveDateTime,
+}
+
+#[derive(Queryable, Selectable, Insertable)]
+#[diesel(table_name = crate::schema::deferred_join_requests)]
+#[diesel(check_for_backend(diesel::pg::Pg))]
+pub struct DeferredJoinRequest {
+    pub chat_id: i64,
+    pub user_id: i64,
+    pub user_chat_id: i64,
+    pub requested_at: NaiveDateTime,
+    pub attempts: i32,
+}
+
+#[derive(Queryable, Selectable)]
+#[diesel(table_name = crate::schema::expiry_history)]
+#[diesel(check_for_backend(diesel::pg::Pg))]
+pub struct ExpiryChange {
+    pub id: i64,
+    pub user_id: i64,
+    pub old_expires_at: Option<NaiveDateTime>,
+    pub new_expires_at: NaiveDateTime,
+    pub changed_at: NaiveDateTime,
+}
+
+#[derive(Insertable)]
+#[diesel(table_name = crate::schema::expiry_history)]
+pub struct NewExpiryChange {
+    pub user_id: i64,
+    pub old_expires_at: Option<NaiveDateTime>,
+    pub new_expires_at: NaiveDateTime,
+}
+
+#[derive(Queryable, Selectable, Insertable)]
+#[diesel(table_name = crate::schema::link_changes)]
+#[diesel(check_for_backend(diesel::pg::Pg))]
+pub struct LinkChange {
+    pub blog: String,
+    pub boosty_id: i64,
+    pub user_id: i64,
+    pub changed_at: NaiveDateTime,
+}
+
+#[derive(Queryable, Selectable)]
+#[diesel(table_name = crate::schema::link_events)]
+#[diesel(check_for_backend(diesel::pg::Pg))]
+pub struct LinkEvent {
+    pub id: i64,
+    pub blog: String,
+    pub boosty_id: i64,
+    pub user_id: i64,
+    pub kind: String,
+    pub created_at: NaiveDateTime,
+}
+
+#[derive(Insertable)]
+#[diesel(table_name = crate::schema::link_events)]
+pub struct NewLinkEvent<'a> {
+    pub blog: &'a str,
+    pub boosty_id: i64,
+    pub user_id: i64,
+    pub kind: &'a str,
+}
+
+#[derive(Queryable, Selectable, Insertable)]
+#[diesel(table_name = crate::schema::account_flags)]
+#[diesel(check_for_backend(diesel::pg::Pg))]
+pub struct AccountFlag {
+    pub blog: String,
+    pub boosty_id: i64,
+    pub blocked: bool,
+    pub flagged_at: NaiveDateTime,
+}
+
+#[derive(Queryable, Selectable)]
+#[diesel(table_name = crate::schema::access_events)]
+#[diesel(check_for_backend(diesel::pg::Pg))]
+pub struct AccessEvent {
+    pub id: i64,
+    pub actor: String,
+    pub user_id: i64,
+    pub boosty_id: Option<i64>,
+    pub chat_id: Option<i64>,
+    pub decision: String,
+    pub reason: String,
+    pub created_at: NaiveDateTime,
+}
+
+#[derive(Insertable)]
+#[diesel(table_name = crate::schema::access_events)]
+pub struct NewAccessEvent<'a> {
+    pub actor: &'a str,
+    pub user_id: i64,
+    pub boosty_id: Option<i64>,
+    pub chat_id: Option<i64>,
+    pub decision: &'a str,
+    pub reason: &'a str,
+}
+
+#[derive(Queryable, Selectable)]
+#[diesel(table_name = crate::schema::user_settings)]
+#[diesel(check_for_backend(diesel::pg::Pg))]
+pub struct UserSettings {
+    pub user_id: i64,
+    pub locale: Option<String>,
+    pub language_code: Option<String>,
+}
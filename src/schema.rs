@@ -1,9 +1,138 @@
 // @generated automatically by Diesel CLI.
 
 diesel::table! {
-    users (id) {
+    access_events (id) {
+        id -> Int8,
+        actor -> Varchar,
+        user_id -> Int8,
+        boosty_id -> Nullable<Int8>,
+        chat_id -> Nullable<Int8>,
+        decision -> Varchar,
+        reason -> Varchar,
+        created_at -> Timestamp,
+    }
+}
+
+diesel::table! {
+    account_flags (blog, boosty_id) {
+        blog -> Varchar,
+        boosty_id -> Int8,
+        blocked -> Bool,
+        flagged_at -> Timestamp,
+    }
+}
+
+diesel::table! {
+    boosty_credentials (name) {
+        name -> Varchar,
+        access_token -> Varchar,
+        refresh_token -> Varchar,
+        device_id -> Varchar,
+        expires_at -> Int8,
+        updated_at -> Timestamp,
+    }
+}
+
+diesel::table! {
+    decline_notices (user_id) {
+        user_id -> Int8,
+        sent_at -> Timestamp,
+    }
+}
+
+diesel::table! {
+    deferred_join_requests (chat_id, user_id) {
+        chat_id -> Int8,
+        user_id -> Int8,
+        user_chat_id -> Int8,
+        requested_at -> Timestamp,
+        attempts -> Int4,
+    }
+}
+
+diesel::table! {
+    expiry_history (id) {
+        id -> Int8,
+        user_id -> Int8,
+        old_expires_at -> Nullable<Timestamp>,
+        new_expires_at -> Timestamp,
+        changed_at -> Timestamp,
+    }
+}
+
+diesel::table! {
+    invite_links (user_id, chat_id) {
+        user_id -> Int8,
+        chat_id -> Int8,
+        invite_link -> Varchar,
+        expires_at -> Timestamp,
+    }
+}
+
+diesel::table! {
+    link_changes (blog, boosty_id) {
+        blog -> Varchar,
+        boosty_id -> Int8,
+        user_id -> Int8,
+        changed_at -> Timestamp,
+    }
+}
+
+diesel::table! {
+    link_events (id) {
+        id -> Int8,
+        blog -> Varchar,
+        boosty_id -> Int8,
+        user_id -> Int8,
+        kind -> Varchar,
+        created_at -> Timestamp,
+    }
+}
+
+diesel::table! {
+    pending_links (id) {
+        id -> Int8,
+        boosty_id -> Int8,
+        blog -> Varchar,
+        email -> Varchar,
+        code -> Varchar,
+        attempts -> Int4,
+        expires_at -> Timestamp,
+        sent_at -> Timestamp,
+    }
+}
+
+diesel::table! {
+    sent_reminders (user_id, expires_at, days) {
+        user_id -> Int8,
+        expires_at -> Timestamp,
+        days -> Int4,
+        sent_at -> Timestamp,
+    }
+}
+
+diesel::table! {
+    user_settings (user_id) {
+        user_id -> Int8,
+        locale -> Nullable<Varchar>,
+        language_code -> Nullable<Varchar>,
+    }
+}
+
+diesel::table! {
+    users (id, blog) {
         id -> Int8,
         boosty_id -> Int8,
         expires_at -> Timestamp,
+        blog -> Varchar,
+        lapsed_at -> Nullable<Timestamp>,
+        suspended -> Bool,
     }
 }
+
+diesel::allow_tables_to_appear_in_same_query!(
+    boosty_credentials,
+    invite_links,
+    sent_reminders,
+    users,
+);
@@ -0,0 +1,235 @@
+use std::time::Duration;
+
+use chrono::Utc;
+
+use crate::{
+    db::{
+        account_flags::{get_account_flag, upsert_account_flag},
+        link_events::{create_link_event, get_link_event_users},
+        Connection,
+    },
+    handlers::notify_user,
+    messages::Text,
+    models::{AccountFlag, NewLinkEvent},
+    translations::Translations,
+    utils::{parse_env, Bot, Config},
+};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LinkEventKind {
+    Link,
+    Unlink,
+    Join,
+}
+
+impl LinkEventKind {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Link => "link",
+            Self::Unlink => "unlink",
+            Self::Join => "join",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Verdict {
+    Allow,
+    Flag,
+    Block,
+}
+
+/// Thresholds on how many distinct Telegram users may link the same Boosty
+/// account, or join with its personal invite links, within `window`. Zero
+/// disables a threshold.
+#[derive(Debug, Clone)]
+pub struct SharingPolicy {
+    pub window: Duration,
+    pub flag_users: usize,
+    pub block_users: usize,
+}
+
+impl Default for SharingPolicy {
+    fn default() -> Self {
+        Self {
+            window: Duration::from_secs(30 * 24 * 60 * 60),
+            flag_users: 3,
+            block_users: 5,
+        }
+    }
+}
+
+impl SharingPolicy {
+    pub fn from_env() -> Self {
+        let default = Self::default();
+
+        Self {
+            window: parse_env::<u64>("SHARING_WINDOW_DAYS").map_or(default.window, |days| {
+                Duration::from_secs(days * 24 * 60 * 60)
+            }),
+            flag_users: parse_env("SHARING_FLAG_USERS").unwrap_or(default.flag_users),
+            block_users: parse_env("SHARING_BLOCK_USERS").unwrap_or(default.block_users),
+        }
+    }
+
+    pub fn evaluate(&self, users: usize) -> Verdict {
+        if self.block_users > 0 && users >= self.block_users {
+            Verdict::Block
+        } else if self.flag_users > 0 && users >= self.flag_users {
+            Verdict::Flag
+        } else {
+            Verdict::Allow
+        }
+    }
+}
+
+pub async fn is_blocked(conn: &mut Connection<'_>, blog: &str, boosty_id: i64) -> bool {
+    get_account_flag(conn, blog, boosty_id)
+        .await
+        .is_ok_and(|account_flag| account_flag.blocked)
+}
+
+pub async fn record_unlink(conn: &mut Connection<'_>, blog: &str, boosty_id: i64, user_id: i64) {
+    let res = create_link_event(
+        conn,
+        NewLinkEvent {
+            blog,
+            boosty_id,
+            user_id,
+            kind: LinkEventKind::Unlink.as_str(),
+        },
+    )
+    .await;
+
+    if let Err(err) = res {
+        error!("Unable to record unlink of {}: {}", user_id, err);
+    }
+}
+
+/// Records a link or join event for the Boosty account and returns the
+/// account's verdict. Admins are notified whenever the verdict escalates.
+/// Database errors are logged and leave the current verdict in place.
+#[allow(clippy::too_many_arguments)]
+pub async fn record_event(
+    translations: &Translations,
+    bot: &Bot,
+    conn: &mut Connection<'_>,
+    config: &Config,
+    blog: &str,
+    boosty_id: i64,
+    user_id: i64,
+    kind: LinkEventKind,
+) -> Verdict {
+    let res = create_link_event(
+        conn,
+        NewLinkEvent {
+            blog,
+            boosty_id,
+            user_id,
+            kind: kind.as_str(),
+        },
+    )
+    .await;
+
+    if let Err(err) = res {
+        error!(
+            "Unable to record `{}` of {}: {}",
+            kind.as_str(),
+            user_id,
+            err
+        );
+    }
+
+    let current = match get_account_flag(conn, blog, boosty_id).await {
+        Ok(account_flag) if account_flag.blocked => Verdict::Block,
+        Ok(_) => Verdict::Flag,
+        Err(_) => Verdict::Allow,
+    };
+
+    let now = Utc::now().naive_utc();
+    let res = get_link_event_users(
+        conn,
+        blog,
+        boosty_id,
+        &[LinkEventKind::Link.as_str(), LinkEventKind::Join.as_str()],
+        now - chrono::Duration::from_std(config.sharing.window).unwrap(),
+    )
+    .await;
+
+    let users = match res {
+        Ok(users) => users,
+        Err(err) => {
+            error!(
+                "Unable to get users of Boosty account {} of `{}`: {}",
+                boosty_id, blog, err
+            );
+            return current;
+        }
+    };
+
+    let verdict = config.sharing.evaluate(users.len());
+
+    if verdict <= current {
+        return current;
+    }
+
+    let res = upsert_account_flag(
+        conn,
+        AccountFlag {
+            blog: blog.to_string(),
+            boosty_id,
+            blocked: verdict == Verdict::Block,
+            flagged_at: now,
+        },
+    )
+    .await;
+
+    if let Err(err) = res {
+        error!(
+            "Unable to flag Boosty account {} of `{}`: {}",
+            boosty_id, blog, err
+        );
+    }
+
+    warn!(
+        "Boosty account {} of `{}` got `{}` verdict after use by {} Telegram users: {:?}",
+        boosty_id,
+        blog,
+        verdict_name(verdict),
+        users.len(),
+        users
+    );
+
+    let tg_ids = users
+        .iter()
+        .map(|id| id.to_string())
+        .collect::<Vec<_>>()
+        .join(", ");
+
+    for admin_id in &config.admins {
+        notify_user(
+            translations,
+            bot,
+            conn,
+            *admin_id,
+            Text::SharingDetected {
+                verdict: verdict_name(verdict),
+                blog: blog.to_string(),
+                boosty_id,
+                users: users.len(),
+                tg_ids: tg_ids.clone(),
+            },
+        )
+        .await;
+    }
+
+    verdict
+}
+
+fn verdict_name(verdict: Verdict) -> &'static str {
+    match verdict {
+        Verdict::Allow => "allow",
+        Verdict::Flag => "flag",
+        Verdict::Block => "block",
+    }
+}
@@ -0,0 +1,408 @@
+use std::{
+    collections::{HashMap, VecDeque},
+    sync::{Arc, Mutex},
+    time::Duration,
+};
+
+use axum::{
+    extract::{Path, Query, State},
+    http::{header, HeaderMap, StatusCode},
+    response::{IntoResponse, Response},
+    routing::{get, post},
+    Json, Router,
+};
+use serde_json::{json, Value};
+
+use crate::boosty_api::{
+    auth::AuthData, types::subscribers::Subscriber, BoostyClient, BoostyClientBuilder, RateLimit,
+    RetryPolicy,
+};
+
+#[derive(Clone, Debug)]
+pub struct MockSubscriber {
+    pub id: u64,
+    pub email: String,
+    pub name: String,
+    pub level_id: u64,
+    pub level_name: String,
+    pub price: f32,
+    pub subscribed: bool,
+    pub black_listed: bool,
+    pub on_time: u64,
+    pub payments: Vec<MockPayment>,
+}
+
+#[derive(Clone, Debug)]
+pub struct MockPayment {
+    pub created_at: u64,
+    pub period: u32,
+}
+
+impl MockPayment {
+    pub fn monthly(created_at: u64) -> Self {
+        Self {
+            created_at,
+            period: 1,
+        }
+    }
+
+    pub fn yearly(created_at: u64) -> Self {
+        Self {
+            created_at,
+            period: 12,
+        }
+    }
+}
+
+impl MockSubscriber {
+    pub fn paid(id: u64, email: &str) -> Self {
+        Self {
+            id,
+            email: email.to_string(),
+            name: format!("Subscriber {}", id),
+            level_id: 1,
+            level_name: "Ёжик".to_string(),
+            price: 300.,
+            subscribed: true,
+            black_listed: false,
+            on_time: 1_717_200_000,
+            payments: vec![],
+        }
+    }
+
+    pub fn expired(id: u64, email: &str) -> Self {
+        Self {
+            price: 0.,
+            subscribed: false,
+            ..Self::paid(id, email)
+        }
+    }
+
+    pub fn black_listed(id: u64, email: &str) -> Self {
+        Self {
+            black_listed: true,
+            ..Self::paid(id, email)
+        }
+    }
+
+    /// The subscriber as the client would parse it from Boosty.
+    pub fn subscriber(&self) -> Subscriber {
+        serde_json::from_value(self.json()).unwrap()
+    }
+
+    fn basic_json(&self) -> Value {
+        json!({
+            "avatarUrl": "",
+            "email": self.email,
+            "hasAvatar": false,
+            "id": self.id,
+            "name": self.name,
+        })
+    }
+
+    fn json(&self) -> Value {
+        let mut value = self.basic_json();
+        let fields = json!({
+            "canWrite": true,
+            "isBlackListed": self.black_listed,
+            "level": {
+                "createdAt": 0,
+                "currencyPrices": { "RUB": self.price },
+                "deleted": false,
+                "id": self.level_id,
+                "isArchived": false,
+                "name": self.level_name,
+                "ownerId": 1,
+                "price": self.price,
+            },
+            "onTime": self.on_time,
+            "payments": self.price,
+            "price": self.price,
+            "subscribed": self.subscribed,
+        });
+
+        value
+            .as_object_mut()
+            .unwrap()
+            .extend(fields.as_object().unwrap().clone());
+
+        value
+    }
+}
+
+#[derive(Clone, Debug)]
+pub enum Fault {
+    RateLimited { retry_after: u64 },
+    ServerError,
+    BadRequest,
+}
+
+#[derive(Debug, Default)]
+struct MockBoostyState {
+    access_token: String,
+    refreshes: usize,
+    blogs: HashMap<String, Vec<MockSubscriber>>,
+    faults: VecDeque<Fault>,
+    payments_down: bool,
+    requests: Vec<String>,
+}
+
+type SharedState = Arc<Mutex<MockBoostyState>>;
+
+#[derive(Clone)]
+pub struct MockBoosty {
+    pub url: String,
+    state: SharedState,
+}
+
+impl MockBoosty {
+    pub async fn start() -> Self {
+        let state = Arc::new(Mutex::new(MockBoostyState {
+            access_token: "token-0".to_string(),
+            ..Default::default()
+        }));
+
+        let app = Router::new()
+            .route("/oauth/token/", post(refresh_token))
+            .route("/v1/blog/:blog/subscribers", get(subscribers))
+            .route("/v1/blog/stat/:blog/search", get(search))
+            .route("/v1/blog/stat/:blog/payments", get(payments))
+            .with_state(state.clone());
+
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url = format!("http://{}", listener.local_addr().unwrap());
+
+        tokio::spawn(async move { axum::serve(listener, app).await.unwrap() });
+
+        Self { url, state }
+    }
+
+    pub fn client(&self) -> BoostyClient {
+        BoostyClientBuilder::new(AuthData::new(
+            "token-0".to_string(),
+            "refresh-0".to_string(),
+            "device".to_string(),
+            u64::MAX as u128,
+        ))
+        .with_custom_base_url(self.url.clone())
+        .with_retry_policy(RetryPolicy {
+            max_attempts: 3,
+            base_delay: Duration::from_millis(1),
+            max_delay: Duration::from_millis(10),
+        })
+        .with_rate_limit(RateLimit {
+            requests_per_second: 1000.,
+            burst: 1000,
+            max_queue: 1000,
+        })
+        .build()
+    }
+
+    pub fn add_subscriber(&self, blog: &str, subscriber: MockSubscriber) {
+        let mut state = self.state.lock().unwrap();
+
+        state
+            .blogs
+            .entry(blog.to_string())
+            .or_default()
+            .retain(|value| value.id != subscriber.id);
+        state.blogs.get_mut(blog).unwrap().push(subscriber);
+    }
+
+    pub fn push_fault(&self, fault: Fault) {
+        self.state.lock().unwrap().faults.push_back(fault);
+    }
+
+    /// Makes the payments endpoint answer with a server error from now on.
+    pub fn fail_payments(&self) {
+        self.state.lock().unwrap().payments_down = true;
+    }
+
+    pub fn revoke_token(&self) {
+        self.state.lock().unwrap().access_token = "revoked".to_string();
+    }
+
+    pub fn refreshes(&self) -> usize {
+        self.state.lock().unwrap().refreshes
+    }
+
+    pub fn requests(&self) -> Vec<String> {
+        self.state.lock().unwrap().requests.clone()
+    }
+}
+
+fn check_request(state: &SharedState, path: String, headers: &HeaderMap) -> Option<Response> {
+    let mut state = state.lock().unwrap();
+    state.requests.push(path);
+
+    if let Some(fault) = state.faults.pop_front() {
+        return Some(match fault {
+            Fault::RateLimited { retry_after } => (
+                StatusCode::TOO_MANY_REQUESTS,
+                [(header::RETRY_AFTER, retry_after.to_string())],
+            )
+                .into_response(),
+            Fault::ServerError => StatusCode::INTERNAL_SERVER_ERROR.into_response(),
+            Fault::BadRequest => StatusCode::BAD_REQUEST.into_response(),
+        });
+    }
+
+    let expected = format!("Bearer {}", state.access_token);
+    let authorized = headers
+        .get(header::AUTHORIZATION)
+        .is_some_and(|value| value.as_bytes() == expected.as_bytes());
+
+    if !authorized {
+        return Some(StatusCode::UNAUTHORIZED.into_response());
+    }
+
+    None
+}
+
+async fn refresh_token(State(state): State<SharedState>) -> Response {
+    let mut state = state.lock().unwrap();
+    state.requests.push("/oauth/token/".to_string());
+    state.refreshes += 1;
+    state.access_token = format!("token-{}", state.refreshes);
+
+    Json(json!({
+        "access_token": state.access_token,
+        "refresh_token": format!("refresh-{}", state.refreshes),
+        "expires_in": 3_600_000,
+    }))
+    .into_response()
+}
+
+async fn subscribers(
+    State(state): State<SharedState>,
+    Path(blog): Path<String>,
+    Query(query): Query<HashMap<String, String>>,
+    headers: HeaderMap,
+) -> Response {
+    if let Some(response) =
+        check_request(&state, format!("/v1/blog/{}/subscribers", blog), &headers)
+    {
+        return response;
+    }
+
+    let user_ids: Option<Vec<u64>> = query.get("user_ids").map(|value| {
+        value
+            .split(',')
+            .filter_map(|id| id.trim().parse().ok())
+            .collect()
+    });
+    let limit: usize = query
+        .get("limit")
+        .and_then(|value| value.parse().ok())
+        .unwrap_or(20);
+    let offset: usize = query
+        .get("offset")
+        .and_then(|value| value.parse().ok())
+        .unwrap_or(0);
+
+    let state = state.lock().unwrap();
+    let matching: Vec<&MockSubscriber> = state
+        .blogs
+        .get(&blog)
+        .map(|subscribers| {
+            subscribers
+                .iter()
+                .filter(|subscriber| {
+                    user_ids
+                        .as_ref()
+                        .is_none_or(|ids| ids.contains(&subscriber.id))
+                })
+                .collect()
+        })
+        .unwrap_or_default();
+
+    let data: Vec<Value> = matching
+        .iter()
+        .skip(offset)
+        .take(limit)
+        .map(|subscriber| subscriber.json())
+        .collect();
+
+    Json(json!({
+        "data": data,
+        "limit": limit,
+        "offset": offset,
+        "total": matching.len(),
+    }))
+    .into_response()
+}
+
+async fn search(
+    State(state): State<SharedState>,
+    Path(blog): Path<String>,
+    Query(query): Query<HashMap<String, String>>,
+    headers: HeaderMap,
+) -> Response {
+    if let Some(response) =
+        check_request(&state, format!("/v1/blog/stat/{}/search", blog), &headers)
+    {
+        return response;
+    }
+
+    let chunk = query.get("chunk").cloned().unwrap_or_default();
+
+    let state = state.lock().unwrap();
+    let data: Vec<Value> = state
+        .blogs
+        .get(&blog)
+        .map(|subscribers| {
+            subscribers
+                .iter()
+                .filter(|subscriber| subscriber.email.contains(&chunk))
+                .map(|subscriber| subscriber.basic_json())
+                .collect()
+        })
+        .unwrap_or_default();
+
+    Json(json!({ "data": data })).into_response()
+}
+
+async fn payments(
+    State(state): State<SharedState>,
+    Path(blog): Path<String>,
+    Query(query): Query<HashMap<String, String>>,
+    headers: HeaderMap,
+) -> Response {
+    if let Some(response) =
+        check_request(&state, format!("/v1/blog/stat/{}/payments", blog), &headers)
+    {
+        return response;
+    }
+
+    if state.lock().unwrap().payments_down {
+        return StatusCode::INTERNAL_SERVER_ERROR.into_response();
+    }
+
+    let user_id: Option<u64> = query.get("user_id").and_then(|value| value.parse().ok());
+
+    let state = state.lock().unwrap();
+    let data: Vec<Value> = state
+        .blogs
+        .get(&blog)
+        .and_then(|subscribers| {
+            subscribers
+                .iter()
+                .find(|subscriber| Some(subscriber.id) == user_id)
+        })
+        .map(|subscriber| {
+            subscriber
+                .payments
+                .iter()
+                .map(|payment| {
+                    json!({
+                        "createdAt": payment.created_at,
+                        "period": payment.period,
+                        "price": subscriber.price,
+                    })
+                })
+                .collect()
+        })
+        .unwrap_or_default();
+
+    Json(json!({ "data": data })).into_response()
+}
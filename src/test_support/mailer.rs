@@ -0,0 +1,52 @@
+use std::sync::{Arc, Mutex};
+
+use async_trait::async_trait;
+
+use crate::mailer::{Mailer, MailerError};
+
+#[derive(Clone, Debug)]
+pub struct SentMail {
+    pub to: String,
+    pub subject: String,
+    pub body: String,
+}
+
+#[derive(Debug, Default)]
+pub struct MemoryMailer {
+    sent: Mutex<Vec<SentMail>>,
+}
+
+impl MemoryMailer {
+    pub fn new() -> Arc<Self> {
+        Arc::new(Self::default())
+    }
+
+    pub fn sent(&self) -> Vec<SentMail> {
+        self.sent.lock().unwrap().clone()
+    }
+
+    /// Extracts the verification code from the last email sent to `to`.
+    pub fn last_code(&self, to: &str) -> Option<String> {
+        self.sent()
+            .iter()
+            .rev()
+            .find(|mail| mail.to == to)?
+            .body
+            .split(|c: char| !c.is_ascii_digit())
+            .find(|chunk| chunk.len() == 6)
+            .map(str::to_string)
+    }
+}
+
+#[async_trait]
+impl Mailer for MemoryMailer {
+    async fn send(&self, to: &str, subject: &str, body: &str) -> Result<(), MailerError> {
+        self.sent.lock().unwrap().push(SentMail {
+            to: to.to_string(),
+            subject: subject.to_string(),
+            body: body.to_string(),
+        });
+
+        Ok(())
+    }
+}
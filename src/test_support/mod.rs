@@ -0,0 +1,136 @@
+pub mod boosty;
+pub mod mailer;
+pub mod telegram;
+
+use std::{env, fs, path::PathBuf};
+
+use diesel_async::{
+    pooled_connection::AsyncDieselConnectionManager, AsyncConnection, AsyncPgConnection,
+    SimpleAsyncConnection,
+};
+use rand::Rng;
+use serde_json::json;
+use teloxide::types::{CallbackQuery, ChatJoinRequest, Message};
+
+use crate::db::Pool;
+
+pub struct TestDb {
+    pub pool: Pool,
+    database_url: String,
+    schema: String,
+}
+
+impl TestDb {
+    /// Creates a fresh schema with every migration applied. Database tests are
+    /// ignored by default; run them with `cargo test -- --include-ignored`
+    /// and `TEST_DATABASE_URL` pointing at a Postgres database.
+    pub async fn create() -> Self {
+        let database_url = env::var("TEST_DATABASE_URL")
+            .expect("Environment variable `TEST_DATABASE_URL` is not provided.");
+
+        let schema = format!("test_{:016x}", rand::thread_rng().gen::<u64>());
+
+        let mut conn = AsyncPgConnection::establish(&database_url).await.unwrap();
+        conn.batch_execute(&format!(
+            "CREATE SCHEMA {schema}; SET search_path TO {schema};"
+        ))
+        .await
+        .unwrap();
+
+        let mut migrations: Vec<PathBuf> =
+            fs::read_dir(concat!(env!("CARGO_MANIFEST_DIR"), "/migrations"))
+                .unwrap()
+                .map(|entry| entry.unwrap().path())
+                .filter(|path| path.is_dir())
+                .collect();
+        migrations.sort();
+
+        for migration in migrations {
+            let up = fs::read_to_string(migration.join("up.sql")).unwrap();
+            conn.batch_execute(&up).await.unwrap();
+        }
+
+        let separator = if database_url.contains('?') { '&' } else { '?' };
+        let pool_url = format!(
+            "{}{}options=-c%20search_path%3D{}",
+            database_url, separator, schema
+        );
+
+        let pool = bb8::Pool::builder()
+            .max_size(4)
+            .build(AsyncDieselConnectionManager::<AsyncPgConnection>::new(
+                pool_url,
+            ))
+            .await
+            .unwrap();
+
+        Self {
+            pool,
+            database_url,
+            schema,
+        }
+    }
+
+    pub async fn cleanup(self) {
+        drop(self.pool);
+
+        let mut conn = AsyncPgConnection::establish(&self.database_url)
+            .await
+            .unwrap();
+        conn.batch_execute(&format!("DROP SCHEMA {} CASCADE;", self.schema))
+            .await
+            .unwrap();
+    }
+}
+
+pub fn private_message(user_id: i64, text: &str) -> Message {
+    serde_json::from_value(json!({
+        "message_id": 1,
+        "date": 0,
+        "chat": { "id": user_id, "type": "private", "first_name": "Test" },
+        "from": { "id": user_id, "is_bot": false, "first_name": "Test" },
+        "text": text,
+    }))
+    .unwrap()
+}
+
+pub fn callback_query(user_id: i64, data: &str) -> CallbackQuery {
+    serde_json::from_value(json!({
+        "id": "1",
+        "from": { "id": user_id, "is_bot": false, "first_name": "Test" },
+        "message": {
+            "message_id": 1,
+            "date": 1,
+            "chat": { "id": user_id, "type": "private", "first_name": "Test" },
+            "text": "",
+        },
+        "chat_instance": "1",
+        "data": data,
+    }))
+    .unwrap()
+}
+
+pub fn join_request(chat_id: i64, user_id: i64) -> ChatJoinRequest {
+    join_request_via(chat_id, user_id, None)
+}
+
+pub fn join_request_via(chat_id: i64, user_id: i64, invite_link: Option<&str>) -> ChatJoinRequest {
+    let mut value = json!({
+        "chat": { "id": chat_id, "type": "supergroup", "title": "Test" },
+        "from": { "id": user_id, "is_bot": false, "first_name": "Test" },
+        "user_chat_id": user_id,
+        "date": 0,
+    });
+
+    if let Some(invite_link) = invite_link {
+        value["invite_link"] = json!({
+            "invite_link": invite_link,
+            "creator": { "id": 123456, "is_bot": true, "first_name": "Bot" },
+            "creates_join_request": true,
+            "is_primary": false,
+            "is_revoked": false,
+        });
+    }
+
+    serde_json::from_value(value).unwrap()
+}
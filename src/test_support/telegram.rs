@@ -0,0 +1,156 @@
+use std::{
+    collections::HashMap,
+    sync::{Arc, Mutex},
+};
+
+use axum::{
+    body::Bytes,
+    extract::{Path, State},
+    response::{IntoResponse, Response},
+    routing::post,
+    Json, Router,
+};
+use serde_json::{json, Value};
+use teloxide::{requests::RequesterExt, types::ParseMode, Bot as TeloxideBot};
+
+use crate::utils::Bot;
+
+#[derive(Debug, Default)]
+struct MockTelegramState {
+    calls: Vec<(String, Value)>,
+    members: HashMap<(i64, i64), String>,
+}
+
+type SharedState = Arc<Mutex<MockTelegramState>>;
+
+#[derive(Clone)]
+pub struct MockTelegram {
+    pub url: String,
+    state: SharedState,
+}
+
+impl MockTelegram {
+    pub async fn start() -> Self {
+        let state = SharedState::default();
+
+        let app = Router::new()
+            .route("/:token/:method", post(call_method))
+            .with_state(state.clone());
+
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url = format!("http://{}", listener.local_addr().unwrap());
+
+        tokio::spawn(async move { axum::serve(listener, app).await.unwrap() });
+
+        Self { url, state }
+    }
+
+    pub fn bot(&self) -> Bot {
+        TeloxideBot::new("123456:TEST")
+            .set_api_url(self.url.parse().unwrap())
+            .parse_mode(ParseMode::Html)
+    }
+
+    pub fn set_member_status(&self, chat_id: i64, user_id: i64, status: &str) {
+        self.state
+            .lock()
+            .unwrap()
+            .members
+            .insert((chat_id, user_id), status.to_string());
+    }
+
+    pub fn calls(&self, method: &str) -> Vec<Value> {
+        self.state
+            .lock()
+            .unwrap()
+            .calls
+            .iter()
+            .filter(|(name, _)| name == method)
+            .map(|(_, payload)| payload.clone())
+            .collect()
+    }
+
+    pub fn sent_texts(&self) -> Vec<String> {
+        self.calls("sendMessage")
+            .iter()
+            .filter_map(|payload| payload["text"].as_str().map(str::to_string))
+            .collect()
+    }
+}
+
+async fn call_method(
+    State(state): State<SharedState>,
+    Path((_token, method)): Path<(String, String)>,
+    body: Bytes,
+) -> Response {
+    let payload: Value = serde_json::from_slice(&body).unwrap_or(Value::Null);
+
+    // Teloxide sends `ApproveChatJoinRequest`, the docs spell it `approveChatJoinRequest`.
+    let mut chars = method.chars();
+    let method: String = chars
+        .next()
+        .map(|first| first.to_ascii_lowercase())
+        .into_iter()
+        .chain(chars)
+        .collect();
+
+    let mut state = state.lock().unwrap();
+    state.calls.push((method.clone(), payload.clone()));
+
+    let result = match method.as_str() {
+        "getChatMember" => {
+            let chat_id = payload["chat_id"].as_i64().unwrap_or_default();
+            let user_id = payload["user_id"].as_i64().unwrap_or_default();
+            let status = state
+                .members
+                .get(&(chat_id, user_id))
+                .cloned()
+                .unwrap_or_else(|| "left".to_string());
+
+            let mut member = json!({
+                "status": status,
+                "user": { "id": user_id, "is_bot": false, "first_name": "Test" },
+            });
+
+            if status == "administrator" {
+                member.as_object_mut().unwrap().extend(
+                    json!({
+                        "is_anonymous": false,
+                        "can_be_edited": false,
+                        "can_manage_chat": true,
+                        "can_change_info": false,
+                        "can_delete_messages": true,
+                        "can_manage_video_chats": false,
+                        "can_invite_users": true,
+                        "can_restrict_members": true,
+                        "can_promote_members": false,
+                    })
+                    .as_object()
+                    .unwrap()
+                    .clone(),
+                );
+            }
+
+            member
+        }
+        "sendMessage" | "editMessageText" => json!({
+            "message_id": payload["message_id"].as_i64().unwrap_or(state.calls.len() as i64),
+            "date": 0,
+            "chat": { "id": payload["chat_id"], "type": "private", "first_name": "Test" },
+            "text": payload["text"],
+        }),
+        "createChatInviteLink" | "revokeChatInviteLink" => json!({
+            "invite_link": payload["invite_link"]
+                .as_str()
+                .map(str::to_string)
+                .unwrap_or_else(|| format!("https://t.me/+invite{}", state.calls.len())),
+            "creator": { "id": 123456, "is_bot": true, "first_name": "Bot" },
+            "creates_join_request": payload["creates_join_request"].as_bool().unwrap_or_default(),
+            "is_primary": false,
+            "is_revoked": method == "revokeChatInviteLink",
+        }),
+        _ => json!(true),
+    };
+
+    Json(json!({ "ok": true, "result": result })).into_response()
+}
@@ -0,0 +1,110 @@
+use std::collections::BTreeSet;
+
+use crate::{
+    access::{AccessMatrix, AccessRule},
+    test_support::boosty::MockSubscriber,
+};
+
+use super::{BLOG, CHAT_ID};
+
+const OTHER_BLOG: &str = "otherblog";
+const VIP_CHAT_ID: i64 = -1002;
+
+fn rule(
+    blog: Option<&str>,
+    levels: Vec<u64>,
+    min_price: Option<f32>,
+    chats: Vec<i64>,
+) -> AccessRule {
+    AccessRule {
+        blog: blog.map(str::to_string),
+        levels,
+        min_price,
+        chats,
+    }
+}
+
+fn chats(chat_ids: &[i64]) -> BTreeSet<i64> {
+    chat_ids.iter().copied().collect()
+}
+
+fn subscriber(level_id: u64, price: f32) -> MockSubscriber {
+    MockSubscriber {
+        level_id,
+        price,
+        ..MockSubscriber::paid(42, "paid@example.com")
+    }
+}
+
+#[test]
+fn levels_filter_grants_listed_levels_only() {
+    let access = AccessMatrix {
+        rules: vec![rule(None, vec![2, 3], None, vec![CHAT_ID])],
+    };
+
+    assert!(access
+        .granted_chats(BLOG, &subscriber(1, 300.).subscriber())
+        .is_empty());
+    assert_eq!(
+        access.granted_chats(BLOG, &subscriber(3, 300.).subscriber()),
+        chats(&[CHAT_ID])
+    );
+}
+
+#[test]
+fn min_price_filter_grants_from_the_price_up() {
+    let access = AccessMatrix {
+        rules: vec![
+            rule(None, vec![], Some(300.), vec![CHAT_ID]),
+            rule(None, vec![], Some(1000.), vec![VIP_CHAT_ID]),
+        ],
+    };
+
+    assert!(access
+        .granted_chats(BLOG, &subscriber(1, 299.).subscriber())
+        .is_empty());
+    assert_eq!(
+        access.granted_chats(BLOG, &subscriber(1, 300.).subscriber()),
+        chats(&[CHAT_ID])
+    );
+    assert_eq!(
+        access.granted_chats(BLOG, &subscriber(2, 1000.).subscriber()),
+        chats(&[CHAT_ID, VIP_CHAT_ID])
+    );
+}
+
+#[test]
+fn blog_filter_grants_subscribers_of_that_blog_only() {
+    let access = AccessMatrix {
+        rules: vec![
+            rule(Some(BLOG), vec![], None, vec![CHAT_ID]),
+            rule(Some(OTHER_BLOG), vec![], None, vec![VIP_CHAT_ID]),
+        ],
+    };
+    let paid = subscriber(1, 300.).subscriber();
+
+    assert_eq!(access.granted_chats(BLOG, &paid), chats(&[CHAT_ID]));
+    assert_eq!(
+        access.granted_chats(OTHER_BLOG, &paid),
+        chats(&[VIP_CHAT_ID])
+    );
+    assert!(access.granted_chats("unknownblog", &paid).is_empty());
+
+    assert_eq!(access.blog_chats(BLOG), chats(&[CHAT_ID]));
+    assert_eq!(access.chats(), chats(&[CHAT_ID, VIP_CHAT_ID]));
+}
+
+#[test]
+fn unpaid_subscribers_are_granted_nothing() {
+    let access = AccessMatrix::single_chat(CHAT_ID);
+
+    for unpaid in [
+        MockSubscriber::expired(42, "expired@example.com"),
+        MockSubscriber::black_listed(43, "banned@example.com"),
+    ] {
+        let unpaid = unpaid.subscriber();
+
+        assert!(access.granted_chats(BLOG, &unpaid).is_empty());
+        assert!(!access.grants(BLOG, &unpaid, CHAT_ID));
+    }
+}
@@ -0,0 +1,223 @@
+use crate::{
+    commands::{handle_admin_command, AdminCommand},
+    db::users::get_user,
+    handlers::chat_join_handler,
+    test_support::{
+        boosty::{MockBoosty, MockSubscriber},
+        join_request, private_message,
+        telegram::MockTelegram,
+        TestDb,
+    },
+};
+
+use super::{config, translations, ADMIN_ID, BLOG, CHAT_ID};
+
+async fn send_admin_command(
+    db: &TestDb,
+    boosty: &MockBoosty,
+    telegram: &MockTelegram,
+    user_id: i64,
+    text: &str,
+    cmd: AdminCommand,
+) -> String {
+    handle_admin_command(
+        translations().await,
+        boosty.client(),
+        db.pool.clone(),
+        config(),
+        telegram.bot(),
+        private_message(user_id, text),
+        cmd,
+    )
+    .await
+    .unwrap();
+
+    // Strip Fluent's bidi isolation marks around placeables.
+    telegram
+        .sent_texts()
+        .pop()
+        .unwrap()
+        .replace(['\u{2068}', '\u{2069}'], "")
+}
+
+#[tokio::test]
+#[ignore = "needs TEST_DATABASE_URL"]
+async fn admin_commands_require_authorization() {
+    let db = TestDb::create().await;
+    let boosty = MockBoosty::start().await;
+    let telegram = MockTelegram::start().await;
+
+    let text = send_admin_command(&db, &boosty, &telegram, 7, "/stats", AdminCommand::Stats).await;
+    assert!(text.contains("только администраторам"));
+
+    telegram.set_member_status(CHAT_ID, 7, "administrator");
+
+    let text = send_admin_command(&db, &boosty, &telegram, 7, "/stats", AdminCommand::Stats).await;
+    assert!(text.contains("Статистика"));
+
+    db.cleanup().await;
+}
+
+#[tokio::test]
+#[ignore = "needs TEST_DATABASE_URL"]
+async fn admin_help_is_translated() {
+    let db = TestDb::create().await;
+    let boosty = MockBoosty::start().await;
+    let telegram = MockTelegram::start().await;
+
+    let text = send_admin_command(
+        &db,
+        &boosty,
+        &telegram,
+        ADMIN_ID,
+        "/admin",
+        AdminCommand::Admin,
+    )
+    .await;
+    assert!(text.contains("Команды администратора"));
+    assert!(text.contains("/recheck"));
+
+    db.cleanup().await;
+}
+
+#[tokio::test]
+#[ignore = "needs TEST_DATABASE_URL"]
+async fn admin_links_looks_up_and_unlinks_users() {
+    let db = TestDb::create().await;
+    let boosty = MockBoosty::start().await;
+    let telegram = MockTelegram::start().await;
+
+    boosty.add_subscriber(BLOG, MockSubscriber::paid(42, "paid@example.com"));
+
+    send_admin_command(
+        &db,
+        &boosty,
+        &telegram,
+        ADMIN_ID,
+        "/link 7 42",
+        AdminCommand::Link {
+            tg_id: 7,
+            boosty_id: 42,
+        },
+    )
+    .await;
+
+    let user = get_user(&mut db.pool.get().await.unwrap(), 7, BLOG)
+        .await
+        .unwrap();
+    assert_eq!(user.boosty_id, 42);
+
+    let text = send_admin_command(
+        &db,
+        &boosty,
+        &telegram,
+        ADMIN_ID,
+        "/lookup paid@example.com",
+        AdminCommand::Lookup("paid@example.com".to_string()),
+    )
+    .await;
+    assert!(text.contains("<code>7</code>"));
+
+    let text = send_admin_command(
+        &db,
+        &boosty,
+        &telegram,
+        ADMIN_ID,
+        "/link 8 42",
+        AdminCommand::Link {
+            tg_id: 8,
+            boosty_id: 42,
+        },
+    )
+    .await;
+    assert!(text.contains("уже привязан"));
+
+    send_admin_command(
+        &db,
+        &boosty,
+        &telegram,
+        ADMIN_ID,
+        "/unlink 7",
+        AdminCommand::Unlink(7),
+    )
+    .await;
+
+    assert!(get_user(&mut db.pool.get().await.unwrap(), 7, BLOG)
+        .await
+        .is_err());
+
+    db.cleanup().await;
+}
+
+#[tokio::test]
+#[ignore = "needs TEST_DATABASE_URL"]
+async fn admin_events_explain_declined_join_requests() {
+    let db = TestDb::create().await;
+    let boosty = MockBoosty::start().await;
+    let telegram = MockTelegram::start().await;
+
+    boosty.add_subscriber(BLOG, MockSubscriber::expired(42, "expired@example.com"));
+
+    send_admin_command(
+        &db,
+        &boosty,
+        &telegram,
+        ADMIN_ID,
+        "/link 7 42",
+        AdminCommand::Link {
+            tg_id: 7,
+            boosty_id: 42,
+        },
+    )
+    .await;
+
+    for user_id in [7, 8] {
+        chat_join_handler(
+            translations().await,
+            boosty.client(),
+            db.pool.clone(),
+            config(),
+            telegram.bot(),
+            join_request(CHAT_ID, user_id),
+        )
+        .await
+        .unwrap();
+    }
+
+    let text = send_admin_command(
+        &db,
+        &boosty,
+        &telegram,
+        ADMIN_ID,
+        "/events 7",
+        AdminCommand::Events(7),
+    )
+    .await;
+    assert!(text.contains("отклонено (unpaid)"));
+    assert!(text.contains(&CHAT_ID.to_string()));
+    assert!(text.contains("join-request"));
+
+    let text = send_admin_command(
+        &db,
+        &boosty,
+        &telegram,
+        ADMIN_ID,
+        "/events 8",
+        AdminCommand::Events(8),
+    )
+    .await;
+    assert!(text.contains("отклонено (not-linked)"));
+
+    let text = send_admin_command(
+        &db,
+        &boosty,
+        &telegram,
+        ADMIN_ID,
+        "/events 9",
+        AdminCommand::Events(9),
+    )
+    .await;
+    assert!(text.contains("Ничего не найдено"));
+
+    db.cleanup().await;
+}
@@ -0,0 +1,152 @@
+use std::time::Duration;
+
+use futures::TryStreamExt;
+use tokio::time::timeout;
+
+use crate::{
+    boosty_api::{
+        types::subscribers::{Order, SortBy, SubscribersRequest},
+        BoostyError, RateLimit, RateLimiter,
+    },
+    test_support::boosty::{Fault, MockBoosty, MockSubscriber},
+};
+
+use super::BLOG;
+
+fn request(user_ids: Vec<u64>, limit: u32) -> SubscribersRequest {
+    SubscribersRequest {
+        user_ids: user_ids.into(),
+        sort_by: SortBy::default(),
+        limit,
+        offset: None,
+        order: Order::default(),
+    }
+}
+
+#[tokio::test]
+async fn subscribers_stream_walks_every_page() {
+    let boosty = MockBoosty::start().await;
+
+    for id in 1..=250 {
+        boosty.add_subscriber(
+            BLOG,
+            MockSubscriber::paid(id, &format!("{}@example.com", id)),
+        );
+    }
+
+    let subscribers: Vec<_> = boosty
+        .client()
+        .subscribers_stream(BLOG, request((1..=250).collect(), 100), 2)
+        .try_collect()
+        .await
+        .unwrap();
+
+    let mut ids: Vec<u64> = subscribers
+        .iter()
+        .map(|value| value.basic_info.id)
+        .collect();
+    ids.sort();
+
+    assert_eq!(ids, (1..=250).collect::<Vec<u64>>());
+}
+
+#[tokio::test]
+async fn rate_limited_request_is_retried() {
+    let boosty = MockBoosty::start().await;
+    boosty.add_subscriber(BLOG, MockSubscriber::paid(1, "paid@example.com"));
+    boosty.push_fault(Fault::RateLimited { retry_after: 0 });
+
+    let response = boosty
+        .client()
+        .subscribers(BLOG, &request(vec![1], 10))
+        .await
+        .unwrap();
+
+    assert_eq!(response.data.len(), 1);
+    assert_eq!(boosty.requests().len(), 2);
+}
+
+#[tokio::test]
+async fn rate_limit_longer_than_max_delay_is_not_retried() {
+    let boosty = MockBoosty::start().await;
+    boosty.add_subscriber(BLOG, MockSubscriber::paid(1, "paid@example.com"));
+    boosty.push_fault(Fault::RateLimited { retry_after: 60 });
+
+    let result = boosty
+        .client()
+        .subscribers(BLOG, &request(vec![1], 10))
+        .await;
+
+    assert!(matches!(result, Err(BoostyError::RateLimited { .. })));
+    assert_eq!(boosty.requests().len(), 1);
+}
+
+#[tokio::test]
+async fn server_errors_surface_after_retries() {
+    let boosty = MockBoosty::start().await;
+
+    for _ in 0..3 {
+        boosty.push_fault(Fault::ServerError);
+    }
+
+    let result = boosty
+        .client()
+        .subscribers(BLOG, &request(vec![1], 10))
+        .await;
+
+    assert!(matches!(result, Err(BoostyError::Server { .. })));
+}
+
+#[tokio::test]
+async fn revoked_token_is_refreshed_once() {
+    let boosty = MockBoosty::start().await;
+    boosty.add_subscriber(BLOG, MockSubscriber::paid(1, "paid@example.com"));
+    boosty.revoke_token();
+
+    let client = boosty.client();
+    let requests = (0..8).map(|_| {
+        let client = client.clone();
+
+        tokio::spawn(async move { client.subscribers(BLOG, &request(vec![1], 10)).await })
+    });
+
+    for handle in futures::future::join_all(requests).await {
+        assert_eq!(handle.unwrap().unwrap().data.len(), 1);
+    }
+
+    assert_eq!(boosty.refreshes(), 1);
+}
+
+#[tokio::test]
+async fn black_listed_flag_is_parsed() {
+    let boosty = MockBoosty::start().await;
+    boosty.add_subscriber(BLOG, MockSubscriber::black_listed(1, "banned@example.com"));
+
+    let response = boosty
+        .client()
+        .subscribers(BLOG, &request(vec![1], 10))
+        .await
+        .unwrap();
+
+    assert!(response.data[0].is_black_listed);
+}
+
+#[tokio::test]
+async fn cancelled_acquire_gives_its_queue_slot_back() {
+    let rate_limiter = RateLimiter::new(RateLimit {
+        requests_per_second: 1.,
+        burst: 1,
+        max_queue: 1,
+    });
+
+    rate_limiter.acquire().await.unwrap();
+
+    // Both wait for the next token; a leaked slot would reject the second one.
+    for _ in 0..2 {
+        assert!(timeout(Duration::from_millis(10), rate_limiter.acquire())
+            .await
+            .is_err());
+    }
+
+    assert_eq!(rate_limiter.metrics().rejected, 0);
+}
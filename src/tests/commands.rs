@@ -0,0 +1,751 @@
+use std::sync::Arc;
+
+use chrono::{Duration, Utc};
+use diesel::prelude::*;
+use diesel_async::RunQueryDsl;
+
+use crate::{
+    commands::{
+        handle_admin_command, handle_command, handle_language_callback, handle_unlink_callback,
+        AdminCommand, Command,
+    },
+    db::{invite_links::get_invite_link, pending_links::get_pending_link, users::get_user},
+    schema::link_changes,
+    test_support::{
+        boosty::{MockBoosty, MockPayment, MockSubscriber},
+        callback_query,
+        mailer::MemoryMailer,
+        private_message,
+        telegram::MockTelegram,
+        TestDb,
+    },
+    utils::Config,
+};
+
+use super::{config, translations, ADMIN_ID, BLOG, CHAT_ID};
+
+#[allow(clippy::too_many_arguments)]
+async fn send_command_with_config(
+    db: &TestDb,
+    boosty: &MockBoosty,
+    telegram: &MockTelegram,
+    mailer: &Arc<MemoryMailer>,
+    config: Arc<Config>,
+    user_id: i64,
+    text: &str,
+    cmd: Command,
+) {
+    handle_command(
+        translations().await,
+        boosty.client(),
+        db.pool.clone(),
+        config,
+        mailer.clone(),
+        telegram.bot(),
+        private_message(user_id, text),
+        cmd,
+    )
+    .await
+    .unwrap();
+}
+
+async fn send_command(
+    db: &TestDb,
+    boosty: &MockBoosty,
+    telegram: &MockTelegram,
+    mailer: &Arc<MemoryMailer>,
+    user_id: i64,
+    text: &str,
+    cmd: Command,
+) {
+    send_command_with_config(db, boosty, telegram, mailer, config(), user_id, text, cmd).await;
+}
+
+async fn send_email(
+    db: &TestDb,
+    boosty: &MockBoosty,
+    telegram: &MockTelegram,
+    mailer: &Arc<MemoryMailer>,
+    user_id: i64,
+    email: &str,
+) {
+    send_command(
+        db,
+        boosty,
+        telegram,
+        mailer,
+        user_id,
+        &format!("/email {}", email),
+        Command::Email(email.to_string()),
+    )
+    .await;
+}
+
+async fn send_code(
+    db: &TestDb,
+    boosty: &MockBoosty,
+    telegram: &MockTelegram,
+    mailer: &Arc<MemoryMailer>,
+    user_id: i64,
+    code: &str,
+) {
+    send_command(
+        db,
+        boosty,
+        telegram,
+        mailer,
+        user_id,
+        &format!("/verify {}", code),
+        Command::Verify(code.to_string()),
+    )
+    .await;
+}
+
+#[tokio::test]
+#[ignore = "needs TEST_DATABASE_URL"]
+async fn email_links_paid_subscriber_after_verification() {
+    let db = TestDb::create().await;
+    let boosty = MockBoosty::start().await;
+    let telegram = MockTelegram::start().await;
+    let mailer = MemoryMailer::new();
+
+    boosty.add_subscriber(BLOG, MockSubscriber::paid(42, "paid@example.com"));
+
+    send_email(&db, &boosty, &telegram, &mailer, 7, "paid@example.com").await;
+
+    assert!(get_user(&mut db.pool.get().await.unwrap(), 7, BLOG)
+        .await
+        .is_err());
+
+    assert!(mailer.sent()[0].subject.contains("HEDGEHOG.INC"));
+
+    let code = mailer.last_code("paid@example.com").unwrap();
+    send_code(&db, &boosty, &telegram, &mailer, 7, &code).await;
+
+    let user = get_user(&mut db.pool.get().await.unwrap(), 7, BLOG)
+        .await
+        .unwrap();
+    assert_eq!(user.boosty_id, 42);
+    assert_eq!(user.blog, BLOG);
+    assert!(telegram
+        .sent_texts()
+        .last()
+        .unwrap()
+        .contains("Subscriber 42"));
+
+    db.cleanup().await;
+}
+
+#[tokio::test]
+#[ignore = "needs TEST_DATABASE_URL"]
+async fn linking_estimates_expiry_when_payments_fail() {
+    let db = TestDb::create().await;
+    let boosty = MockBoosty::start().await;
+    let telegram = MockTelegram::start().await;
+    let mailer = MemoryMailer::new();
+
+    boosty.add_subscriber(BLOG, MockSubscriber::paid(42, "paid@example.com"));
+    boosty.fail_payments();
+
+    send_email(&db, &boosty, &telegram, &mailer, 7, "paid@example.com").await;
+    let code = mailer.last_code("paid@example.com").unwrap();
+    send_code(&db, &boosty, &telegram, &mailer, 7, &code).await;
+
+    let user = get_user(&mut db.pool.get().await.unwrap(), 7, BLOG)
+        .await
+        .unwrap();
+    assert_eq!(user.boosty_id, 42);
+
+    db.cleanup().await;
+}
+
+#[tokio::test]
+#[ignore = "needs TEST_DATABASE_URL"]
+async fn user_links_accounts_of_several_blogs() {
+    const OTHER_BLOG: &str = "otherblog";
+
+    let db = TestDb::create().await;
+    let boosty = MockBoosty::start().await;
+    let telegram = MockTelegram::start().await;
+    let mailer = MemoryMailer::new();
+    let config = Arc::new(Config {
+        blogs: vec![BLOG.to_string(), OTHER_BLOG.to_string()],
+        ..(*config()).clone()
+    });
+
+    boosty.add_subscriber(BLOG, MockSubscriber::paid(42, "paid@example.com"));
+    boosty.add_subscriber(OTHER_BLOG, MockSubscriber::paid(43, "paid@example.com"));
+
+    for _ in 0..2 {
+        let email = "paid@example.com";
+        send_command_with_config(
+            &db,
+            &boosty,
+            &telegram,
+            &mailer,
+            config.clone(),
+            7,
+            &format!("/email {}", email),
+            Command::Email(email.to_string()),
+        )
+        .await;
+
+        let code = mailer.last_code(email).unwrap();
+        send_command_with_config(
+            &db,
+            &boosty,
+            &telegram,
+            &mailer,
+            config.clone(),
+            7,
+            &format!("/verify {}", code),
+            Command::Verify(code),
+        )
+        .await;
+    }
+
+    let mut conn = db.pool.get().await.unwrap();
+    assert_eq!(get_user(&mut conn, 7, BLOG).await.unwrap().boosty_id, 42);
+    assert_eq!(
+        get_user(&mut conn, 7, OTHER_BLOG).await.unwrap().boosty_id,
+        43
+    );
+    drop(conn);
+
+    db.cleanup().await;
+}
+
+#[tokio::test]
+#[ignore = "needs TEST_DATABASE_URL"]
+async fn linked_user_gets_personal_invite_link() {
+    let db = TestDb::create().await;
+    let boosty = MockBoosty::start().await;
+    let telegram = MockTelegram::start().await;
+    let mailer = MemoryMailer::new();
+
+    boosty.add_subscriber(BLOG, MockSubscriber::paid(42, "paid@example.com"));
+
+    send_email(&db, &boosty, &telegram, &mailer, 7, "paid@example.com").await;
+    let code = mailer.last_code("paid@example.com").unwrap();
+    send_code(&db, &boosty, &telegram, &mailer, 7, &code).await;
+
+    let created = telegram.calls("createChatInviteLink");
+    assert_eq!(created.len(), 1);
+    assert_eq!(created[0]["chat_id"], CHAT_ID);
+    assert_eq!(created[0]["creates_join_request"], true);
+
+    let invite_link = get_invite_link(&mut db.pool.get().await.unwrap(), 7, CHAT_ID)
+        .await
+        .unwrap()
+        .invite_link;
+    assert!(telegram.sent_texts().last().unwrap().contains(&invite_link));
+
+    send_command(
+        &db,
+        &boosty,
+        &telegram,
+        &mailer,
+        7,
+        "/profile",
+        Command::Profile,
+    )
+    .await;
+
+    let profile = telegram
+        .sent_texts()
+        .pop()
+        .unwrap()
+        .replace(['\u{2068}', '\u{2069}'], "");
+    assert!(profile.contains("</i>\n\n⚠️ <i>Внимание!"));
+    assert!(profile.contains(&invite_link));
+
+    assert!(telegram.calls("revokeChatInviteLink").is_empty());
+    assert_eq!(telegram.calls("createChatInviteLink").len(), 1);
+
+    db.cleanup().await;
+}
+
+#[tokio::test]
+#[ignore = "needs TEST_DATABASE_URL"]
+async fn profile_shows_expiry_from_payments() {
+    let db = TestDb::create().await;
+    let boosty = MockBoosty::start().await;
+    let telegram = MockTelegram::start().await;
+    let mailer = MemoryMailer::new();
+
+    boosty.add_subscriber(
+        BLOG,
+        MockSubscriber {
+            payments: vec![MockPayment::yearly(1_719_792_000)],
+            ..MockSubscriber::paid(42, "paid@example.com")
+        },
+    );
+
+    link_account(&db, &boosty, &telegram, &mailer, 7, "paid@example.com").await;
+    send_command(
+        &db,
+        &boosty,
+        &telegram,
+        &mailer,
+        7,
+        "/profile",
+        Command::Profile,
+    )
+    .await;
+
+    let profile = telegram.sent_texts().pop().unwrap();
+    assert!(profile.contains("2025-07-01 00:00:00"));
+    assert!(!profile.contains("Внимание!"));
+
+    db.cleanup().await;
+}
+
+#[tokio::test]
+#[ignore = "needs TEST_DATABASE_URL"]
+async fn verification_rejects_wrong_codes_until_attempts_run_out() {
+    let db = TestDb::create().await;
+    let boosty = MockBoosty::start().await;
+    let telegram = MockTelegram::start().await;
+    let mailer = MemoryMailer::new();
+
+    boosty.add_subscriber(BLOG, MockSubscriber::paid(42, "paid@example.com"));
+
+    send_email(&db, &boosty, &telegram, &mailer, 7, "paid@example.com").await;
+    let code = mailer.last_code("paid@example.com").unwrap();
+    let wrong_code = if code == "000000" { "111111" } else { "000000" };
+
+    for _ in 0..config().verification_attempts {
+        send_code(&db, &boosty, &telegram, &mailer, 7, wrong_code).await;
+    }
+    send_code(&db, &boosty, &telegram, &mailer, 7, &code).await;
+
+    assert!(get_user(&mut db.pool.get().await.unwrap(), 7, BLOG)
+        .await
+        .is_err());
+
+    db.cleanup().await;
+}
+
+#[tokio::test]
+#[ignore = "needs TEST_DATABASE_URL"]
+async fn email_again_keeps_verification_attempts() {
+    let db = TestDb::create().await;
+    let boosty = MockBoosty::start().await;
+    let telegram = MockTelegram::start().await;
+    let mailer = MemoryMailer::new();
+
+    boosty.add_subscriber(BLOG, MockSubscriber::paid(42, "paid@example.com"));
+
+    send_email(&db, &boosty, &telegram, &mailer, 7, "paid@example.com").await;
+    let code = mailer.last_code("paid@example.com").unwrap();
+    let wrong_code = if code == "000000" { "111111" } else { "000000" };
+
+    for _ in 0..config().verification_attempts {
+        send_code(&db, &boosty, &telegram, &mailer, 7, wrong_code).await;
+    }
+    send_email(&db, &boosty, &telegram, &mailer, 7, "paid@example.com").await;
+
+    let pending_link = get_pending_link(&mut db.pool.get().await.unwrap(), 7)
+        .await
+        .unwrap();
+    assert_eq!(pending_link.attempts, config().verification_attempts);
+    assert_eq!(mailer.sent().len(), 1);
+
+    db.cleanup().await;
+}
+
+#[tokio::test]
+#[ignore = "needs TEST_DATABASE_URL"]
+async fn email_is_rate_limited_per_user_and_address() {
+    let db = TestDb::create().await;
+    let boosty = MockBoosty::start().await;
+    let telegram = MockTelegram::start().await;
+    let mailer = MemoryMailer::new();
+    let config = Arc::new(Config {
+        verification_cooldown: std::time::Duration::from_secs(60),
+        ..(*config()).clone()
+    });
+
+    boosty.add_subscriber(BLOG, MockSubscriber::paid(42, "paid@example.com"));
+
+    for user_id in [7, 7, 8] {
+        handle_command(
+            translations().await,
+            boosty.client(),
+            db.pool.clone(),
+            config.clone(),
+            mailer.clone(),
+            telegram.bot(),
+            private_message(user_id, "/email paid@example.com"),
+            Command::Email("paid@example.com".to_string()),
+        )
+        .await
+        .unwrap();
+    }
+
+    assert_eq!(mailer.sent().len(), 1);
+
+    db.cleanup().await;
+}
+
+#[tokio::test]
+#[ignore = "needs TEST_DATABASE_URL"]
+async fn email_does_not_link_expired_subscriber() {
+    let db = TestDb::create().await;
+    let boosty = MockBoosty::start().await;
+    let telegram = MockTelegram::start().await;
+    let mailer = MemoryMailer::new();
+
+    boosty.add_subscriber(BLOG, MockSubscriber::expired(42, "expired@example.com"));
+
+    send_email(&db, &boosty, &telegram, &mailer, 7, "expired@example.com").await;
+
+    assert!(mailer.sent().is_empty());
+    assert_eq!(telegram.sent_texts().len(), 1);
+
+    db.cleanup().await;
+}
+
+async fn link_account(
+    db: &TestDb,
+    boosty: &MockBoosty,
+    telegram: &MockTelegram,
+    mailer: &Arc<MemoryMailer>,
+    user_id: i64,
+    email: &str,
+) {
+    send_email(db, boosty, telegram, mailer, user_id, email).await;
+    let code = mailer.last_code(email).unwrap();
+    send_code(db, boosty, telegram, mailer, user_id, &code).await;
+}
+
+async fn expire_relink_cooldown(db: &TestDb) {
+    diesel::update(link_changes::table)
+        .set(link_changes::changed_at.eq((Utc::now() - Duration::days(31)).naive_utc()))
+        .execute(&mut db.pool.get().await.unwrap())
+        .await
+        .unwrap();
+}
+
+async fn press_button(db: &TestDb, telegram: &MockTelegram, user_id: i64, data: &str) {
+    handle_unlink_callback(
+        translations().await,
+        db.pool.clone(),
+        config(),
+        telegram.bot(),
+        callback_query(user_id, data),
+    )
+    .await
+    .unwrap();
+}
+
+#[tokio::test]
+#[ignore = "needs TEST_DATABASE_URL"]
+async fn email_does_not_relink_during_cooldown() {
+    let db = TestDb::create().await;
+    let boosty = MockBoosty::start().await;
+    let telegram = MockTelegram::start().await;
+    let mailer = MemoryMailer::new();
+
+    boosty.add_subscriber(BLOG, MockSubscriber::paid(42, "paid@example.com"));
+
+    link_account(&db, &boosty, &telegram, &mailer, 7, "paid@example.com").await;
+
+    send_email(&db, &boosty, &telegram, &mailer, 8, "paid@example.com").await;
+
+    assert_eq!(mailer.sent().len(), 1);
+    assert!(get_user(&mut db.pool.get().await.unwrap(), 8, BLOG)
+        .await
+        .is_err());
+
+    db.cleanup().await;
+}
+
+#[tokio::test]
+#[ignore = "needs TEST_DATABASE_URL"]
+async fn unlink_does_not_skip_relink_cooldown() {
+    let db = TestDb::create().await;
+    let boosty = MockBoosty::start().await;
+    let telegram = MockTelegram::start().await;
+    let mailer = MemoryMailer::new();
+
+    boosty.add_subscriber(BLOG, MockSubscriber::paid(42, "paid@example.com"));
+
+    link_account(&db, &boosty, &telegram, &mailer, 7, "paid@example.com").await;
+    press_button(&db, &telegram, 7, "unlink:confirm").await;
+    assert!(get_user(&mut db.pool.get().await.unwrap(), 7, BLOG)
+        .await
+        .is_err());
+
+    send_email(&db, &boosty, &telegram, &mailer, 8, "paid@example.com").await;
+
+    assert_eq!(mailer.sent().len(), 1);
+    assert!(telegram
+        .sent_texts()
+        .last()
+        .unwrap()
+        .contains("недавно был привязан"));
+
+    expire_relink_cooldown(&db).await;
+
+    link_account(&db, &boosty, &telegram, &mailer, 8, "paid@example.com").await;
+    assert_eq!(
+        get_user(&mut db.pool.get().await.unwrap(), 8, BLOG)
+            .await
+            .unwrap()
+            .boosty_id,
+        42
+    );
+
+    db.cleanup().await;
+}
+
+#[tokio::test]
+#[ignore = "needs TEST_DATABASE_URL"]
+async fn relink_after_cooldown_moves_account() {
+    let db = TestDb::create().await;
+    let boosty = MockBoosty::start().await;
+    let telegram = MockTelegram::start().await;
+    let mailer = MemoryMailer::new();
+
+    boosty.add_subscriber(BLOG, MockSubscriber::paid(42, "paid@example.com"));
+    telegram.set_member_status(CHAT_ID, 7, "member");
+
+    link_account(&db, &boosty, &telegram, &mailer, 7, "paid@example.com").await;
+
+    expire_relink_cooldown(&db).await;
+
+    link_account(&db, &boosty, &telegram, &mailer, 8, "paid@example.com").await;
+
+    let mut conn = db.pool.get().await.unwrap();
+    assert!(get_user(&mut conn, 7, BLOG).await.is_err());
+    assert_eq!(get_user(&mut conn, 8, BLOG).await.unwrap().boosty_id, 42);
+    drop(conn);
+
+    let kicked = telegram.calls("kickChatMember");
+    assert_eq!(kicked.len(), 1);
+    assert_eq!(kicked[0]["user_id"], 7);
+    assert!(telegram
+        .calls("sendMessage")
+        .iter()
+        .any(|payload| payload["chat_id"] == 7
+            && payload["text"]
+                .as_str()
+                .unwrap()
+                .contains("был привязан к другому")));
+
+    send_email(&db, &boosty, &telegram, &mailer, 7, "paid@example.com").await;
+    assert_eq!(mailer.sent().len(), 2);
+
+    db.cleanup().await;
+}
+
+#[tokio::test]
+#[ignore = "needs TEST_DATABASE_URL"]
+async fn shared_account_is_flagged_then_blocked() {
+    let db = TestDb::create().await;
+    let boosty = MockBoosty::start().await;
+    let telegram = MockTelegram::start().await;
+    let mailer = MemoryMailer::new();
+
+    boosty.add_subscriber(BLOG, MockSubscriber::paid(42, "paid@example.com"));
+
+    let admin_texts = || {
+        telegram
+            .calls("sendMessage")
+            .into_iter()
+            .filter(|payload| payload["chat_id"] == ADMIN_ID)
+            .map(|payload| {
+                payload["text"]
+                    .as_str()
+                    .unwrap()
+                    .replace(['\u{2068}', '\u{2069}'], "")
+            })
+            .collect::<Vec<_>>()
+    };
+
+    link_account(&db, &boosty, &telegram, &mailer, 7, "paid@example.com").await;
+    assert!(admin_texts().is_empty());
+
+    expire_relink_cooldown(&db).await;
+    link_account(&db, &boosty, &telegram, &mailer, 8, "paid@example.com").await;
+    assert_eq!(admin_texts().len(), 1);
+    assert_eq!(
+        get_user(&mut db.pool.get().await.unwrap(), 8, BLOG)
+            .await
+            .unwrap()
+            .boosty_id,
+        42
+    );
+
+    expire_relink_cooldown(&db).await;
+    link_account(&db, &boosty, &telegram, &mailer, 9, "paid@example.com").await;
+
+    let texts = admin_texts();
+    assert_eq!(texts.len(), 2);
+    assert!(texts[1].contains("заблокирован"));
+    assert!(texts[1].contains(&format!("/unblock {} 42", BLOG)));
+
+    let mut conn = db.pool.get().await.unwrap();
+    assert!(get_user(&mut conn, 8, BLOG).await.is_ok());
+    assert!(get_user(&mut conn, 9, BLOG).await.is_err());
+    drop(conn);
+
+    send_email(&db, &boosty, &telegram, &mailer, 10, "paid@example.com").await;
+    assert_eq!(mailer.sent().len(), 3);
+
+    handle_admin_command(
+        translations().await,
+        boosty.client(),
+        db.pool.clone(),
+        config(),
+        telegram.bot(),
+        private_message(ADMIN_ID, &format!("/unblock {} 42", BLOG)),
+        AdminCommand::Unblock {
+            blog: BLOG.to_string(),
+            boosty_id: 42,
+        },
+    )
+    .await
+    .unwrap();
+    assert!(admin_texts().last().unwrap().contains("снята"));
+
+    expire_relink_cooldown(&db).await;
+    link_account(&db, &boosty, &telegram, &mailer, 10, "paid@example.com").await;
+
+    assert_eq!(
+        get_user(&mut db.pool.get().await.unwrap(), 10, BLOG)
+            .await
+            .unwrap()
+            .boosty_id,
+        42
+    );
+    assert_eq!(admin_texts().len(), 3);
+
+    db.cleanup().await;
+}
+
+#[tokio::test]
+#[ignore = "needs TEST_DATABASE_URL"]
+async fn unlink_requires_confirmation() {
+    let db = TestDb::create().await;
+    let boosty = MockBoosty::start().await;
+    let telegram = MockTelegram::start().await;
+    let mailer = MemoryMailer::new();
+
+    boosty.add_subscriber(BLOG, MockSubscriber::paid(42, "paid@example.com"));
+    boosty.add_subscriber(BLOG, MockSubscriber::paid(43, "other@example.com"));
+    telegram.set_member_status(CHAT_ID, 7, "member");
+    telegram.set_member_status(CHAT_ID, 8, "member");
+
+    link_account(&db, &boosty, &telegram, &mailer, 7, "paid@example.com").await;
+    link_account(&db, &boosty, &telegram, &mailer, 8, "other@example.com").await;
+
+    send_command(
+        &db,
+        &boosty,
+        &telegram,
+        &mailer,
+        7,
+        "/unlink",
+        Command::Unlink,
+    )
+    .await;
+
+    let prompt = telegram.calls("sendMessage").pop().unwrap();
+    let buttons = prompt["reply_markup"]["inline_keyboard"]
+        .as_array()
+        .unwrap();
+    assert_eq!(buttons.len(), 2);
+
+    press_button(&db, &telegram, 7, "unlink:cancel").await;
+    assert!(get_user(&mut db.pool.get().await.unwrap(), 7, BLOG)
+        .await
+        .is_ok());
+
+    // Unlinking always removes the membership, even from an old keyboard.
+    press_button(&db, &telegram, 7, "unlink:confirm").await;
+    press_button(&db, &telegram, 8, "unlink:leave").await;
+
+    let mut conn = db.pool.get().await.unwrap();
+    assert!(get_user(&mut conn, 7, BLOG).await.is_err());
+    assert!(get_user(&mut conn, 8, BLOG).await.is_err());
+    drop(conn);
+
+    assert_eq!(telegram.calls("answerCallbackQuery").len(), 3);
+    assert_eq!(telegram.calls("editMessageText").len(), 3);
+
+    let kicked = telegram.calls("kickChatMember");
+    assert_eq!(kicked.len(), 2);
+    assert_eq!(kicked[0]["user_id"], 7);
+    assert_eq!(kicked[1]["user_id"], 8);
+
+    db.cleanup().await;
+}
+
+#[tokio::test]
+#[ignore = "needs TEST_DATABASE_URL"]
+async fn language_follows_telegram_then_user_choice() {
+    let db = TestDb::create().await;
+    let boosty = MockBoosty::start().await;
+    let telegram = MockTelegram::start().await;
+    let mailer = MemoryMailer::new();
+
+    let mut msg = private_message(7, "/start");
+    msg.from.as_mut().unwrap().language_code = Some("en".to_string());
+
+    handle_command(
+        translations().await,
+        boosty.client(),
+        db.pool.clone(),
+        config(),
+        mailer.clone(),
+        telegram.bot(),
+        msg,
+        Command::Start,
+    )
+    .await
+    .unwrap();
+
+    assert!(telegram.sent_texts().pop().unwrap().contains("Hi!"));
+
+    send_command(
+        &db,
+        &boosty,
+        &telegram,
+        &mailer,
+        7,
+        "/language",
+        Command::Language,
+    )
+    .await;
+
+    let prompt = telegram.calls("sendMessage").pop().unwrap();
+    let buttons = prompt["reply_markup"]["inline_keyboard"]
+        .as_array()
+        .unwrap();
+    assert_eq!(buttons.len(), 2);
+
+    handle_language_callback(
+        translations().await,
+        db.pool.clone(),
+        telegram.bot(),
+        callback_query(7, "language:ru-RU"),
+    )
+    .await
+    .unwrap();
+
+    send_command(
+        &db,
+        &boosty,
+        &telegram,
+        &mailer,
+        7,
+        "/start",
+        Command::Start,
+    )
+    .await;
+    assert!(telegram.sent_texts().pop().unwrap().contains("Привет!"));
+
+    db.cleanup().await;
+}
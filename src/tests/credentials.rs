@@ -0,0 +1,108 @@
+use std::{env, fs, path::PathBuf};
+
+use crate::boosty_api::{
+    auth::AuthData,
+    credentials::{CredentialError, CredentialStore, EnvCredentialStore, FileCredentialStore},
+};
+
+fn auth_data(access_token: &str) -> AuthData {
+    AuthData::new(
+        access_token.to_string(),
+        "refresh".to_string(),
+        "device".to_string(),
+        1_717_200_000_000,
+    )
+}
+
+fn credentials_dir(name: &str) -> PathBuf {
+    let dir = env::temp_dir().join(format!(
+        "hedgehog-credentials-{}-{}",
+        name,
+        std::process::id()
+    ));
+    fs::create_dir_all(&dir).unwrap();
+
+    dir
+}
+
+#[tokio::test]
+async fn file_store_replaces_credentials_through_a_temp_file() {
+    let dir = credentials_dir("save");
+    let path = dir.join("auth.toml");
+    let tmp_path = dir.join("auth.toml.tmp");
+    let store = FileCredentialStore::new(&path);
+
+    store.save(&auth_data("old")).await.unwrap();
+    // Left behind by a save that was interrupted before the rename.
+    fs::write(&tmp_path, "garbage").unwrap();
+
+    store.save(&auth_data("new")).await.unwrap();
+
+    assert!(!tmp_path.exists());
+    assert_eq!(store.load().await.unwrap().access_token, "new");
+
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+
+        let mode = fs::metadata(&path).unwrap().permissions().mode();
+        assert_eq!(mode & 0o777, 0o600);
+    }
+
+    fs::remove_dir_all(&dir).unwrap();
+}
+
+#[tokio::test]
+async fn file_store_reports_malformed_credentials() {
+    let dir = credentials_dir("load");
+    let path = dir.join("auth.toml");
+    let store = FileCredentialStore::new(&path);
+
+    assert!(matches!(store.load().await, Err(CredentialError::Io(_))));
+
+    fs::write(&path, "access-token = ").unwrap();
+    assert!(matches!(
+        store.load().await,
+        Err(CredentialError::Format(_))
+    ));
+
+    fs::remove_dir_all(&dir).unwrap();
+}
+
+// A single test, since the variables are shared by the whole process.
+#[tokio::test]
+async fn env_store_maps_missing_and_malformed_variables() {
+    let vars = [
+        ("BOOSTY_ACCESS_TOKEN", "access"),
+        ("BOOSTY_REFRESH_TOKEN", "refresh"),
+        ("BOOSTY_DEVICE_ID", "device"),
+        ("BOOSTY_EXPIRES_AT", "1717200000000"),
+    ];
+
+    for (name, _) in vars {
+        env::remove_var(name);
+    }
+
+    let err = EnvCredentialStore.load().await.unwrap_err();
+    assert!(matches!(err, CredentialError::Missing(what) if what.contains("BOOSTY_EXPIRES_AT")));
+
+    env::set_var("BOOSTY_EXPIRES_AT", "soon");
+    let err = EnvCredentialStore.load().await.unwrap_err();
+    assert!(matches!(err, CredentialError::Format(_)));
+
+    env::set_var("BOOSTY_EXPIRES_AT", "1717200000000");
+    let err = EnvCredentialStore.load().await.unwrap_err();
+    assert!(matches!(err, CredentialError::Missing(what) if what.contains("BOOSTY_ACCESS_TOKEN")));
+
+    for (name, value) in vars {
+        env::set_var(name, value);
+    }
+
+    let auth_data = EnvCredentialStore.load().await.unwrap();
+    assert_eq!(auth_data.access_token, "access");
+    assert_eq!(auth_data.expires_at, 1_717_200_000_000);
+
+    for (name, _) in vars {
+        env::remove_var(name);
+    }
+}
@@ -0,0 +1,839 @@
+use std::sync::Arc;
+
+use chrono::{DateTime, Duration, Utc};
+use diesel::prelude::*;
+use diesel_async::RunQueryDsl;
+
+use crate::{
+    access::{AccessMatrix, AccessRule},
+    db::{
+        account_flags::{get_account_flag, upsert_account_flag},
+        invite_links::upsert_invite_link,
+        users::{create_user, get_user, update_user_lapse},
+    },
+    handlers::{
+        chat_join_handler, chat_subscribers_checker, deferred_join_requests_retrier,
+        expiry_reminders_sender, ReconciliationReport,
+    },
+    models::{AccountFlag, ExpiryChange, InviteLink, User},
+    schema::{deferred_join_requests, expiry_history, link_events},
+    test_support::{
+        boosty::{Fault, MockBoosty, MockPayment, MockSubscriber},
+        join_request_via,
+        telegram::MockTelegram,
+        TestDb,
+    },
+    utils::Config,
+};
+
+use super::{config, config_without_grace, translations, ADMIN_ID, BLOG, CHAT_ID};
+
+async fn link_user(db: &TestDb, id: i64, boosty_id: i64) {
+    link_user_until(db, id, boosty_id, Utc::now()).await;
+}
+
+async fn link_user_until(db: &TestDb, id: i64, boosty_id: i64, expires_at: DateTime<Utc>) {
+    create_user(
+        &mut db.pool.get().await.unwrap(),
+        User {
+            id,
+            boosty_id,
+            expires_at: expires_at.naive_utc(),
+            blog: BLOG.to_string(),
+            lapsed_at: None,
+            suspended: false,
+        },
+    )
+    .await
+    .unwrap();
+}
+
+async fn request_join(db: &TestDb, boosty: &MockBoosty, telegram: &MockTelegram, user_id: i64) {
+    request_join_via(db, boosty, telegram, user_id, None).await;
+}
+
+async fn request_join_via(
+    db: &TestDb,
+    boosty: &MockBoosty,
+    telegram: &MockTelegram,
+    user_id: i64,
+    invite_link: Option<&str>,
+) {
+    chat_join_handler(
+        translations().await,
+        boosty.client(),
+        db.pool.clone(),
+        config(),
+        telegram.bot(),
+        join_request_via(CHAT_ID, user_id, invite_link),
+    )
+    .await
+    .unwrap();
+}
+
+async fn issue_invite_link(db: &TestDb, user_id: i64, invite_link: &str) {
+    upsert_invite_link(
+        &mut db.pool.get().await.unwrap(),
+        InviteLink {
+            user_id,
+            chat_id: CHAT_ID,
+            invite_link: invite_link.to_string(),
+            expires_at: (Utc::now() + Duration::days(1)).naive_utc(),
+        },
+    )
+    .await
+    .unwrap();
+}
+
+#[tokio::test]
+#[ignore = "needs TEST_DATABASE_URL"]
+async fn join_approves_paid_subscriber() {
+    let db = TestDb::create().await;
+    let boosty = MockBoosty::start().await;
+    let telegram = MockTelegram::start().await;
+
+    boosty.add_subscriber(BLOG, MockSubscriber::paid(42, "paid@example.com"));
+    link_user(&db, 7, 42).await;
+
+    request_join(&db, &boosty, &telegram, 7).await;
+
+    assert_eq!(telegram.calls("approveChatJoinRequest").len(), 1);
+    assert!(telegram.calls("declineChatJoinRequest").is_empty());
+
+    db.cleanup().await;
+}
+
+#[tokio::test]
+#[ignore = "needs TEST_DATABASE_URL"]
+async fn join_declines_unlinked_expired_and_blacklisted_users() {
+    let db = TestDb::create().await;
+    let boosty = MockBoosty::start().await;
+    let telegram = MockTelegram::start().await;
+
+    boosty.add_subscriber(BLOG, MockSubscriber::expired(42, "expired@example.com"));
+    boosty.add_subscriber(BLOG, MockSubscriber::black_listed(43, "banned@example.com"));
+    link_user(&db, 7, 42).await;
+    link_user(&db, 8, 43).await;
+
+    for user_id in [6, 7, 8] {
+        request_join(&db, &boosty, &telegram, user_id).await;
+    }
+
+    assert!(telegram.calls("approveChatJoinRequest").is_empty());
+    assert_eq!(telegram.calls("declineChatJoinRequest").len(), 3);
+
+    db.cleanup().await;
+}
+
+#[tokio::test]
+#[ignore = "needs TEST_DATABASE_URL"]
+async fn declined_users_are_told_why_once() {
+    let db = TestDb::create().await;
+    let boosty = MockBoosty::start().await;
+    let telegram = MockTelegram::start().await;
+
+    boosty.add_subscriber(BLOG, MockSubscriber::expired(42, "expired@example.com"));
+    link_user(&db, 7, 42).await;
+
+    for user_id in [6, 7, 6, 7] {
+        request_join(&db, &boosty, &telegram, user_id).await;
+    }
+
+    assert_eq!(telegram.calls("declineChatJoinRequest").len(), 4);
+
+    let sent = telegram.calls("sendMessage");
+    assert_eq!(sent.len(), 2);
+
+    assert_eq!(sent[0]["chat_id"], 6);
+    assert!(sent[0]["text"].as_str().unwrap().contains("/email"));
+    assert_eq!(
+        sent[0]["reply_markup"]["inline_keyboard"][0][0]["callback_data"],
+        "email:start"
+    );
+
+    assert_eq!(sent[1]["chat_id"], 7);
+    assert!(sent[1]["text"]
+        .as_str()
+        .unwrap()
+        .contains("нет активной подписки"));
+    assert_eq!(
+        sent[1]["reply_markup"]["inline_keyboard"][0][0]["url"],
+        format!("https://boosty.to/{}", BLOG)
+    );
+
+    // The cooldown is per user, so a different reason isn't explained either.
+    boosty.push_fault(Fault::BadRequest);
+    request_join(&db, &boosty, &telegram, 7).await;
+
+    assert_eq!(telegram.calls("declineChatJoinRequest").len(), 5);
+    assert_eq!(telegram.calls("sendMessage").len(), 2);
+
+    db.cleanup().await;
+}
+
+#[tokio::test]
+#[ignore = "needs TEST_DATABASE_URL"]
+async fn join_declines_blocked_account() {
+    let db = TestDb::create().await;
+    let boosty = MockBoosty::start().await;
+    let telegram = MockTelegram::start().await;
+
+    boosty.add_subscriber(BLOG, MockSubscriber::paid(42, "paid@example.com"));
+    link_user(&db, 7, 42).await;
+
+    upsert_account_flag(
+        &mut db.pool.get().await.unwrap(),
+        AccountFlag {
+            blog: BLOG.to_string(),
+            boosty_id: 42,
+            blocked: true,
+            flagged_at: Utc::now().naive_utc(),
+        },
+    )
+    .await
+    .unwrap();
+
+    request_join(&db, &boosty, &telegram, 7).await;
+
+    assert!(telegram.calls("approveChatJoinRequest").is_empty());
+    assert_eq!(telegram.calls("declineChatJoinRequest").len(), 1);
+
+    db.cleanup().await;
+}
+
+#[tokio::test]
+#[ignore = "needs TEST_DATABASE_URL"]
+async fn join_through_someone_elses_personal_link_is_declined() {
+    let db = TestDb::create().await;
+    let boosty = MockBoosty::start().await;
+    let telegram = MockTelegram::start().await;
+
+    boosty.add_subscriber(BLOG, MockSubscriber::paid(42, "paid@example.com"));
+    link_user(&db, 7, 42).await;
+    issue_invite_link(&db, 7, "https://t.me/+personal7").await;
+
+    for user_id in [8, 7] {
+        request_join_via(
+            &db,
+            &boosty,
+            &telegram,
+            user_id,
+            Some("https://t.me/+personal7"),
+        )
+        .await;
+    }
+
+    let declined = telegram.calls("declineChatJoinRequest");
+    assert_eq!(declined.len(), 1);
+    assert_eq!(declined[0]["user_id"], 8);
+
+    let approved = telegram.calls("approveChatJoinRequest");
+    assert_eq!(approved.len(), 1);
+    assert_eq!(approved[0]["user_id"], 7);
+
+    assert!(telegram
+        .sent_texts()
+        .iter()
+        .any(|text| text.contains("/profile")));
+
+    db.cleanup().await;
+}
+
+#[tokio::test]
+#[ignore = "needs TEST_DATABASE_URL"]
+async fn joins_through_a_forwarded_personal_link_flag_then_block_the_account() {
+    let db = TestDb::create().await;
+    let boosty = MockBoosty::start().await;
+    let telegram = MockTelegram::start().await;
+
+    boosty.add_subscriber(BLOG, MockSubscriber::paid(42, "paid@example.com"));
+    link_user(&db, 7, 42).await;
+    issue_invite_link(&db, 7, "https://t.me/+personal7").await;
+
+    for user_id in [8, 9] {
+        request_join_via(
+            &db,
+            &boosty,
+            &telegram,
+            user_id,
+            Some("https://t.me/+personal7"),
+        )
+        .await;
+    }
+
+    let account_flag = get_account_flag(&mut db.pool.get().await.unwrap(), BLOG, 42)
+        .await
+        .unwrap();
+    assert!(!account_flag.blocked);
+
+    request_join_via(&db, &boosty, &telegram, 10, Some("https://t.me/+personal7")).await;
+
+    let account_flag = get_account_flag(&mut db.pool.get().await.unwrap(), BLOG, 42)
+        .await
+        .unwrap();
+    assert!(account_flag.blocked);
+    assert_eq!(
+        telegram
+            .calls("sendMessage")
+            .iter()
+            .filter(|payload| payload["chat_id"] == ADMIN_ID)
+            .count(),
+        2
+    );
+
+    request_join_via(&db, &boosty, &telegram, 7, Some("https://t.me/+personal7")).await;
+
+    assert!(telegram.calls("approveChatJoinRequest").is_empty());
+    assert_eq!(telegram.calls("declineChatJoinRequest").len(), 4);
+
+    db.cleanup().await;
+}
+
+#[tokio::test]
+#[ignore = "needs TEST_DATABASE_URL"]
+async fn join_survives_revoked_token_and_rate_limit() {
+    let db = TestDb::create().await;
+    let boosty = MockBoosty::start().await;
+    let telegram = MockTelegram::start().await;
+
+    boosty.add_subscriber(BLOG, MockSubscriber::paid(42, "paid@example.com"));
+    link_user(&db, 7, 42).await;
+
+    boosty.revoke_token();
+    boosty.push_fault(Fault::RateLimited { retry_after: 0 });
+
+    request_join(&db, &boosty, &telegram, 7).await;
+
+    assert_eq!(boosty.refreshes(), 1);
+    assert_eq!(telegram.calls("approveChatJoinRequest").len(), 1);
+
+    db.cleanup().await;
+}
+
+#[tokio::test]
+#[ignore = "needs TEST_DATABASE_URL"]
+async fn join_is_deferred_during_outage_and_retried() {
+    let db = TestDb::create().await;
+    let boosty = MockBoosty::start().await;
+    let telegram = MockTelegram::start().await;
+
+    boosty.add_subscriber(BLOG, MockSubscriber::paid(42, "paid@example.com"));
+    link_user(&db, 7, 42).await;
+    link_user(&db, 8, 43).await;
+
+    for _ in 0..6 {
+        boosty.push_fault(Fault::ServerError);
+    }
+
+    request_join(&db, &boosty, &telegram, 7).await;
+    request_join(&db, &boosty, &telegram, 8).await;
+
+    assert!(telegram.calls("approveChatJoinRequest").is_empty());
+    assert!(telegram.calls("declineChatJoinRequest").is_empty());
+
+    diesel::update(deferred_join_requests::table.filter(deferred_join_requests::user_id.eq(8)))
+        .set(deferred_join_requests::requested_at.eq((Utc::now() - Duration::days(2)).naive_utc()))
+        .execute(&mut db.pool.get().await.unwrap())
+        .await
+        .unwrap();
+
+    for _ in 0..6 {
+        boosty.push_fault(Fault::ServerError);
+    }
+
+    let retry = || async {
+        deferred_join_requests_retrier(
+            translations().await,
+            boosty.client(),
+            db.pool.clone(),
+            config(),
+            telegram.bot(),
+        )
+        .await
+        .unwrap();
+    };
+
+    retry().await;
+
+    assert!(telegram.calls("approveChatJoinRequest").is_empty());
+    let declined = telegram.calls("declineChatJoinRequest");
+    assert_eq!(declined.len(), 1);
+    assert_eq!(declined[0]["user_id"], 8);
+
+    retry().await;
+
+    let approved = telegram.calls("approveChatJoinRequest");
+    assert_eq!(approved.len(), 1);
+    assert_eq!(approved[0]["user_id"], 7);
+
+    let remaining: i64 = deferred_join_requests::table
+        .count()
+        .get_result(&mut db.pool.get().await.unwrap())
+        .await
+        .unwrap();
+    assert_eq!(remaining, 0);
+
+    let join_events: i64 = link_events::table
+        .filter(link_events::user_id.eq(7))
+        .count()
+        .get_result(&mut db.pool.get().await.unwrap())
+        .await
+        .unwrap();
+    assert_eq!(join_events, 1);
+
+    db.cleanup().await;
+}
+
+#[tokio::test]
+#[ignore = "needs TEST_DATABASE_URL"]
+async fn join_is_declined_on_permanent_boosty_error() {
+    let db = TestDb::create().await;
+    let boosty = MockBoosty::start().await;
+    let telegram = MockTelegram::start().await;
+
+    boosty.add_subscriber(BLOG, MockSubscriber::paid(42, "paid@example.com"));
+    link_user(&db, 7, 42).await;
+    boosty.push_fault(Fault::BadRequest);
+
+    request_join(&db, &boosty, &telegram, 7).await;
+
+    let declined = telegram.calls("declineChatJoinRequest");
+    assert_eq!(declined.len(), 1);
+    assert_eq!(declined[0]["user_id"], 7);
+
+    let deferred: i64 = deferred_join_requests::table
+        .count()
+        .get_result(&mut db.pool.get().await.unwrap())
+        .await
+        .unwrap();
+    assert_eq!(deferred, 0);
+
+    db.cleanup().await;
+}
+
+#[tokio::test]
+#[ignore = "needs TEST_DATABASE_URL"]
+async fn checker_keeps_paid_members() {
+    let db = TestDb::create().await;
+    let boosty = MockBoosty::start().await;
+    let telegram = MockTelegram::start().await;
+
+    boosty.add_subscriber(BLOG, MockSubscriber::paid(42, "paid@example.com"));
+    link_user(&db, 7, 42).await;
+    telegram.set_member_status(CHAT_ID, 7, "member");
+
+    chat_subscribers_checker(
+        translations().await,
+        boosty.client(),
+        db.pool.clone(),
+        config(),
+        telegram.bot(),
+    )
+    .await
+    .unwrap();
+
+    assert!(telegram.calls("kickChatMember").is_empty());
+    assert!(get_user(&mut db.pool.get().await.unwrap(), 7, BLOG)
+        .await
+        .is_ok());
+
+    db.cleanup().await;
+}
+
+#[tokio::test]
+#[ignore = "needs TEST_DATABASE_URL"]
+async fn checker_removes_downgraded_member_from_higher_level_chats() {
+    const VIP_CHAT_ID: i64 = -1002;
+
+    let db = TestDb::create().await;
+    let boosty = MockBoosty::start().await;
+    let telegram = MockTelegram::start().await;
+    let config = Arc::new(Config {
+        access: AccessMatrix {
+            rules: vec![
+                AccessRule {
+                    blog: None,
+                    levels: vec![],
+                    min_price: Some(300.),
+                    chats: vec![CHAT_ID],
+                },
+                AccessRule {
+                    blog: None,
+                    levels: vec![],
+                    min_price: Some(1000.),
+                    chats: vec![VIP_CHAT_ID],
+                },
+            ],
+        },
+        ..(*config()).clone()
+    });
+
+    boosty.add_subscriber(BLOG, MockSubscriber::paid(42, "paid@example.com"));
+    link_user_until(&db, 7, 42, Utc::now() + Duration::days(10)).await;
+    telegram.set_member_status(CHAT_ID, 7, "member");
+    telegram.set_member_status(VIP_CHAT_ID, 7, "member");
+
+    let report = chat_subscribers_checker(
+        translations().await,
+        boosty.client(),
+        db.pool.clone(),
+        config,
+        telegram.bot(),
+    )
+    .await
+    .unwrap();
+
+    assert_eq!(report.kicked, 1);
+    assert_eq!(report.removed, 0);
+
+    let kicked = telegram.calls("kickChatMember");
+    assert_eq!(kicked.len(), 1);
+    assert_eq!(kicked[0]["chat_id"].as_i64(), Some(VIP_CHAT_ID));
+    assert_eq!(kicked[0]["user_id"].as_i64(), Some(7));
+    assert!(get_user(&mut db.pool.get().await.unwrap(), 7, BLOG)
+        .await
+        .is_ok());
+
+    db.cleanup().await;
+}
+
+#[tokio::test]
+#[ignore = "needs TEST_DATABASE_URL"]
+async fn checker_removes_lapsed_subscriber() {
+    let db = TestDb::create().await;
+    let boosty = MockBoosty::start().await;
+    let telegram = MockTelegram::start().await;
+
+    boosty.add_subscriber(BLOG, MockSubscriber::paid(42, "paid@example.com"));
+    boosty.add_subscriber(BLOG, MockSubscriber::expired(43, "expired@example.com"));
+    link_user(&db, 7, 42).await;
+    link_user(&db, 8, 43).await;
+    link_user(&db, 9, 44).await;
+    telegram.set_member_status(CHAT_ID, 7, "member");
+    telegram.set_member_status(CHAT_ID, 8, "member");
+    telegram.set_member_status(CHAT_ID, 9, "member");
+
+    let report = chat_subscribers_checker(
+        translations().await,
+        boosty.client(),
+        db.pool.clone(),
+        config_without_grace(),
+        telegram.bot(),
+    )
+    .await
+    .unwrap();
+
+    assert_eq!(
+        report,
+        ReconciliationReport {
+            checked: 3,
+            unpaid: 1,
+            not_found: 1,
+            kicked: 2,
+            refreshed: 1,
+            removed: 2,
+            ..Default::default()
+        }
+    );
+
+    // Boosty no longer knowing 44 is treated as a lapsed subscription.
+    let mut kicked: Vec<_> = telegram
+        .calls("kickChatMember")
+        .iter()
+        .map(|payload| payload["user_id"].as_i64().unwrap())
+        .collect();
+    kicked.sort();
+    assert_eq!(kicked, vec![8, 9]);
+    assert_eq!(telegram.calls("unbanChatMember").len(), 2);
+
+    let mut conn = db.pool.get().await.unwrap();
+    assert!(get_user(&mut conn, 7, BLOG).await.is_ok());
+    assert!(get_user(&mut conn, 8, BLOG).await.is_err());
+    assert!(get_user(&mut conn, 9, BLOG).await.is_err());
+    drop(conn);
+
+    let mut sent: Vec<_> = telegram
+        .calls("sendMessage")
+        .iter()
+        .map(|payload| payload["chat_id"].as_i64().unwrap())
+        .collect();
+    sent.sort();
+    assert_eq!(sent, vec![8, 9]);
+
+    db.cleanup().await;
+}
+
+#[tokio::test]
+#[ignore = "needs TEST_DATABASE_URL"]
+async fn checker_suspends_after_grace_and_restores_on_renewal() {
+    let db = TestDb::create().await;
+    let boosty = MockBoosty::start().await;
+    let telegram = MockTelegram::start().await;
+
+    boosty.add_subscriber(BLOG, MockSubscriber::expired(42, "expired@example.com"));
+    link_user(&db, 7, 42).await;
+    telegram.set_member_status(CHAT_ID, 7, "member");
+
+    let run_checker = || async {
+        chat_subscribers_checker(
+            translations().await,
+            boosty.client(),
+            db.pool.clone(),
+            config(),
+            telegram.bot(),
+        )
+        .await
+        .unwrap()
+    };
+
+    let report = run_checker().await;
+    assert_eq!(report.in_grace, 1);
+    assert!(telegram.calls("kickChatMember").is_empty());
+    assert_eq!(telegram.calls("sendMessage").len(), 1);
+
+    let mut conn = db.pool.get().await.unwrap();
+    let user = get_user(&mut conn, 7, BLOG).await.unwrap();
+    assert!(user.lapsed_at.is_some());
+    assert!(!user.suspended);
+
+    update_user_lapse(
+        &mut conn,
+        7,
+        BLOG,
+        Some((Utc::now() - Duration::days(4)).naive_utc()),
+        false,
+    )
+    .await
+    .unwrap();
+    drop(conn);
+
+    let report = run_checker().await;
+    assert_eq!(report.suspended, 1);
+    assert_eq!(report.kicked, 1);
+
+    let user = get_user(&mut db.pool.get().await.unwrap(), 7, BLOG)
+        .await
+        .unwrap();
+    assert!(user.suspended);
+
+    boosty.add_subscriber(BLOG, MockSubscriber::paid(42, "expired@example.com"));
+
+    let report = run_checker().await;
+    assert_eq!(report.restored, 1);
+    assert_eq!(telegram.calls("createChatInviteLink").len(), 1);
+
+    let user = get_user(&mut db.pool.get().await.unwrap(), 7, BLOG)
+        .await
+        .unwrap();
+    assert!(!user.suspended);
+    assert!(user.lapsed_at.is_none());
+
+    db.cleanup().await;
+}
+
+#[tokio::test]
+#[ignore = "needs TEST_DATABASE_URL"]
+async fn checker_removes_blacklisted_subscriber_without_grace() {
+    let db = TestDb::create().await;
+    let boosty = MockBoosty::start().await;
+    let telegram = MockTelegram::start().await;
+
+    boosty.add_subscriber(BLOG, MockSubscriber::black_listed(42, "banned@example.com"));
+    link_user_until(&db, 7, 42, Utc::now() + Duration::days(10)).await;
+    telegram.set_member_status(CHAT_ID, 7, "member");
+
+    let report = chat_subscribers_checker(
+        translations().await,
+        boosty.client(),
+        db.pool.clone(),
+        config(),
+        telegram.bot(),
+    )
+    .await
+    .unwrap();
+
+    assert_eq!(report.removed, 1);
+    assert_eq!(report.in_grace, 0);
+    assert_eq!(report.kicked, 1);
+    assert!(get_user(&mut db.pool.get().await.unwrap(), 7, BLOG)
+        .await
+        .is_err());
+
+    let texts = telegram.sent_texts();
+    assert_eq!(texts.len(), 1);
+    assert!(texts[0].contains("доступ к группе закрыт"));
+
+    db.cleanup().await;
+}
+
+#[tokio::test]
+#[ignore = "needs TEST_DATABASE_URL"]
+async fn checker_refreshes_expiry_from_yearly_payment() {
+    let db = TestDb::create().await;
+    let boosty = MockBoosty::start().await;
+    let telegram = MockTelegram::start().await;
+
+    // A monthly subscription switched to the yearly plan on 2024-07-01.
+    boosty.add_subscriber(
+        BLOG,
+        MockSubscriber {
+            payments: vec![
+                MockPayment::monthly(1_717_200_000),
+                MockPayment::yearly(1_719_792_000),
+            ],
+            ..MockSubscriber::paid(42, "paid@example.com")
+        },
+    );
+    link_user(&db, 7, 42).await;
+
+    for _ in 0..2 {
+        chat_subscribers_checker(
+            translations().await,
+            boosty.client(),
+            db.pool.clone(),
+            config(),
+            telegram.bot(),
+        )
+        .await
+        .unwrap();
+    }
+
+    let mut conn = db.pool.get().await.unwrap();
+    let expected = DateTime::from_timestamp(1_751_328_000, 0)
+        .unwrap()
+        .naive_utc();
+
+    let user = get_user(&mut conn, 7, BLOG).await.unwrap();
+    assert_eq!(user.expires_at, expected);
+
+    let history: Vec<ExpiryChange> = expiry_history::table
+        .filter(expiry_history::user_id.eq(7))
+        .load(&mut conn)
+        .await
+        .unwrap();
+    assert_eq!(history.len(), 1);
+    assert_eq!(history[0].new_expires_at, expected);
+    drop(conn);
+
+    db.cleanup().await;
+}
+
+#[tokio::test]
+#[ignore = "needs TEST_DATABASE_URL"]
+async fn checker_keeps_expiry_when_payments_fail() {
+    let db = TestDb::create().await;
+    let boosty = MockBoosty::start().await;
+    let telegram = MockTelegram::start().await;
+
+    boosty.add_subscriber(
+        BLOG,
+        MockSubscriber {
+            payments: vec![MockPayment::yearly(1_719_792_000)],
+            ..MockSubscriber::paid(42, "paid@example.com")
+        },
+    );
+    boosty.fail_payments();
+
+    let expires_at =
+        DateTime::from_timestamp(Utc::now().timestamp(), 0).unwrap() + Duration::days(100);
+    link_user_until(&db, 7, 42, expires_at).await;
+
+    let report = chat_subscribers_checker(
+        translations().await,
+        boosty.client(),
+        db.pool.clone(),
+        config(),
+        telegram.bot(),
+    )
+    .await
+    .unwrap();
+
+    assert_eq!(report.checked, 1);
+    assert_eq!(report.refreshed, 0);
+
+    let user = get_user(&mut db.pool.get().await.unwrap(), 7, BLOG)
+        .await
+        .unwrap();
+    assert_eq!(user.expires_at, expires_at.naive_utc());
+
+    db.cleanup().await;
+}
+
+#[tokio::test]
+#[ignore = "needs TEST_DATABASE_URL"]
+async fn checker_survives_boosty_outage() {
+    let db = TestDb::create().await;
+    let boosty = MockBoosty::start().await;
+    let telegram = MockTelegram::start().await;
+
+    boosty.add_subscriber(BLOG, MockSubscriber::expired(42, "expired@example.com"));
+    link_user(&db, 7, 42).await;
+    telegram.set_member_status(CHAT_ID, 7, "member");
+
+    for _ in 0..3 {
+        boosty.push_fault(Fault::ServerError);
+    }
+
+    chat_subscribers_checker(
+        translations().await,
+        boosty.client(),
+        db.pool.clone(),
+        config(),
+        telegram.bot(),
+    )
+    .await
+    .unwrap();
+
+    assert!(telegram.calls("kickChatMember").is_empty());
+    assert!(get_user(&mut db.pool.get().await.unwrap(), 7, BLOG)
+        .await
+        .is_ok());
+
+    db.cleanup().await;
+}
+
+#[tokio::test]
+#[ignore = "needs TEST_DATABASE_URL"]
+async fn reminders_are_sent_once_per_threshold() {
+    let db = TestDb::create().await;
+    let telegram = MockTelegram::start().await;
+
+    link_user_until(&db, 7, 42, Utc::now() + Duration::hours(60)).await;
+    link_user_until(&db, 8, 43, Utc::now() + Duration::days(20)).await;
+
+    let send_reminders = || async {
+        expiry_reminders_sender(
+            translations().await,
+            db.pool.clone(),
+            config(),
+            telegram.bot(),
+        )
+        .await
+        .unwrap();
+    };
+
+    send_reminders().await;
+
+    let expires_at = get_user(&mut db.pool.get().await.unwrap(), 7, BLOG)
+        .await
+        .unwrap()
+        .expires_at
+        .format("%Y-%m-%d %H:%M:%S")
+        .to_string();
+
+    let sent = telegram.calls("sendMessage");
+    assert_eq!(sent.len(), 1);
+    assert_eq!(sent[0]["chat_id"], 7);
+
+    let text = sent[0]["text"]
+        .as_str()
+        .unwrap()
+        .replace(['\u{2068}', '\u{2069}'], "");
+    assert!(text.contains("Осталось 3 дня"));
+    assert!(text.contains(&expires_at));
+
+    send_reminders().await;
+    assert_eq!(telegram.calls("sendMessage").len(), 1);
+
+    db.cleanup().await;
+}
@@ -0,0 +1,57 @@
+mod access;
+mod admin;
+mod boosty_client;
+mod commands;
+mod credentials;
+mod handlers;
+mod translations;
+
+use std::{path::PathBuf, sync::Arc, time::Duration};
+
+use crate::{
+    access::AccessMatrix, sharing::SharingPolicy, translations::Translations, utils::Config,
+};
+
+const BLOG: &str = "hedgehoginc";
+const CHAT_ID: i64 = -1001;
+const ADMIN_ID: i64 = 1;
+
+fn config() -> Arc<Config> {
+    Arc::new(Config {
+        access: AccessMatrix::single_chat(CHAT_ID),
+        admins: vec![ADMIN_ID],
+        blogs: vec![BLOG.to_string()],
+        verification_ttl: Duration::from_secs(15 * 60),
+        verification_attempts: 3,
+        verification_cooldown: Duration::ZERO,
+        invite_link_ttl: Duration::from_secs(24 * 60 * 60),
+        reminder_days: vec![1, 3, 7],
+        grace_period: Duration::from_secs(3 * 24 * 60 * 60),
+        suspension_period: Duration::from_secs(30 * 24 * 60 * 60),
+        relink_cooldown: Duration::from_secs(30 * 24 * 60 * 60),
+        sharing: SharingPolicy {
+            window: Duration::from_secs(30 * 24 * 60 * 60),
+            flag_users: 2,
+            block_users: 3,
+        },
+        decline_notice_cooldown: Duration::from_secs(60 * 60),
+        join_retry_interval: Duration::from_secs(60),
+        join_request_max_age: Duration::from_secs(24 * 60 * 60),
+        translations_dir: PathBuf::from(concat!(
+            env!("CARGO_MANIFEST_DIR"),
+            "/assets/translations"
+        )),
+    })
+}
+
+fn config_without_grace() -> Arc<Config> {
+    Arc::new(Config {
+        grace_period: Duration::ZERO,
+        suspension_period: Duration::ZERO,
+        ..(*config()).clone()
+    })
+}
+
+async fn translations() -> Arc<Translations> {
+    Arc::new(Translations::load(&config().translations_dir).unwrap())
+}
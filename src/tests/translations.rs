@@ -0,0 +1,141 @@
+use std::{collections::BTreeSet, env, fs, path::PathBuf};
+
+use regex::Regex;
+
+use crate::{
+    messages::{render, Text},
+    translations::{TranslationError, Translations, DEFAULT_LOCALE},
+};
+
+use super::config;
+
+fn copy_translations(name: &str) -> PathBuf {
+    let dir = env::temp_dir().join(format!(
+        "hedgehog-translations-{}-{}",
+        name,
+        std::process::id()
+    ));
+    fs::create_dir_all(&dir).unwrap();
+
+    for entry in fs::read_dir(&config().translations_dir).unwrap() {
+        let path = entry.unwrap().path();
+        fs::copy(&path, dir.join(path.file_name().unwrap())).unwrap();
+    }
+
+    dir
+}
+
+fn unlink_button_cancel(translations: &Translations) -> String {
+    render(
+        &translations.get("en-US").unwrap(),
+        Text::UnlinkButtonCancel,
+    )
+}
+
+#[test]
+fn bundled_translations_include_default_locale() {
+    let translations = Translations::load(&config().translations_dir).unwrap();
+
+    assert!(translations
+        .locales()
+        .iter()
+        .any(|locale| *locale == DEFAULT_LOCALE));
+}
+
+/// Splits an FTL file into messages, each with the variables it references.
+fn ftl_messages(source: &str) -> Vec<(&str, BTreeSet<&str>)> {
+    let variable = Regex::new(r"\$([a-zA-Z][a-zA-Z0-9_-]*)").unwrap();
+    let mut messages: Vec<(&str, BTreeSet<&str>)> = vec![];
+
+    for line in source.lines() {
+        if line.starts_with('#') {
+            continue;
+        }
+
+        if !line.starts_with(' ') {
+            if let Some((id, _)) = line.split_once(" =") {
+                messages.push((id, BTreeSet::new()));
+            }
+        }
+
+        if let Some((_, variables)) = messages.last_mut() {
+            variables.extend(
+                variable
+                    .captures_iter(line)
+                    .map(|captures| captures.get(1).unwrap().as_str()),
+            );
+        }
+    }
+
+    messages
+}
+
+#[test]
+fn catalogue_matches_every_ftl_file() {
+    for entry in fs::read_dir(&config().translations_dir).unwrap() {
+        let path = entry.unwrap().path();
+        let source = fs::read_to_string(&path).unwrap();
+        let messages = ftl_messages(&source);
+
+        for (id, args) in Text::IDS.iter().zip(Text::ARGS) {
+            let Some((_, variables)) = messages.iter().find(|(value, _)| value == id) else {
+                panic!("`{}` is missing from {:?}", id, path);
+            };
+
+            let args: BTreeSet<_> = args.iter().map(|arg| arg.replace('_', "-")).collect();
+            let variables: BTreeSet<_> = variables.iter().map(|value| value.to_string()).collect();
+
+            assert_eq!(
+                args, variables,
+                "`{}` in {:?} doesn't use exactly the catalogue arguments",
+                id, path
+            );
+        }
+
+        for (id, _) in &messages {
+            assert!(
+                Text::IDS.contains(id),
+                "`{}` from {:?} isn't in the catalogue",
+                id,
+                path
+            );
+        }
+    }
+}
+
+#[test]
+fn missing_message_is_reported_and_reload_keeps_old_bundles() {
+    let dir = copy_translations("reload");
+    let path = dir.join("en-US.ftl");
+    let translations = Translations::load(&dir).unwrap();
+
+    let source = fs::read_to_string(&path).unwrap();
+    fs::write(&path, source.replace("unlink-button-cancel = Cancel", "")).unwrap();
+
+    match translations.reload() {
+        Err(TranslationError::MissingMessages(locale, ids)) => {
+            assert_eq!(locale, "en-US");
+            assert_eq!(ids, vec!["unlink-button-cancel"]);
+        }
+        _ => panic!("Expected a missing message error."),
+    }
+    assert_eq!(unlink_button_cancel(&translations), "Cancel");
+    assert!(matches!(
+        Translations::load(&dir),
+        Err(TranslationError::MissingMessages(..))
+    ));
+
+    fs::write(
+        &path,
+        source.replace(
+            "unlink-button-cancel = Cancel",
+            "unlink-button-cancel = Never mind",
+        ),
+    )
+    .unwrap();
+
+    translations.reload().unwrap();
+    assert_eq!(unlink_button_cancel(&translations), "Never mind");
+
+    fs::remove_dir_all(dir).unwrap();
+}
@@ -1,21 +1,222 @@
+use std::{
+    fmt::Display,
+    fs,
+    path::{Path, PathBuf},
+    sync::{Arc, RwLock},
+};
+
 use fluent::{bundle::FluentBundle, FluentResource};
 use intl_memoizer::concurrent::IntlLangMemoizer;
-use unic_langid::langid;
+use unic_langid::LanguageIdentifier;
+
+use crate::{
+    db::{
+        user_settings::{get_user_settings, upsert_user_language_code},
+        Connection,
+    },
+    messages::Text,
+};
 
 pub type TranslationType = FluentBundle<FluentResource, IntlLangMemoizer>;
 
-pub async fn load_langs() -> TranslationType {
-    let ftl_string = include_str!("../assets/translations/ru-RU.ftl").to_string();
-    let res = FluentResource::try_new(ftl_string).expect("Failed to parse an FTL string.");
+type Bundles = Vec<(LanguageIdentifier, Arc<TranslationType>)>;
 
-    let langid_ru = langid!("ru-RU");
-    let mut lang_bundle: TranslationType = FluentBundle::new_concurrent(vec![langid_ru]);
+pub const DEFAULT_LOCALE: &str = "ru-RU";
+
+#[derive(Debug)]
+pub enum TranslationError {
+    Io(PathBuf, std::io::Error),
+    Parse(PathBuf, String),
+    MissingDefault(PathBuf),
+    MissingMessages(String, Vec<&'static str>),
+}
 
-    {
-        lang_bundle
-            .add_resource(res)
-            .expect("Failed to add FTL resources to the bundle.");
+impl Display for TranslationError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Io(path, err) => write!(f, "Unable to read `{}`: {}", path.display(), err),
+            Self::Parse(path, err) => write!(f, "Unable to parse `{}`: {}", path.display(), err),
+            Self::MissingDefault(dir) => write!(
+                f,
+                "`{}` has no `{}.ftl` file",
+                dir.display(),
+                DEFAULT_LOCALE
+            ),
+            Self::MissingMessages(locale, ids) => write!(
+                f,
+                "Locale `{}` is missing messages: {}",
+                locale,
+                ids.join(", ")
+            ),
+        }
     }
+}
+
+impl std::error::Error for TranslationError {}
+
+/// Bundles for every `.ftl` file in the translations directory, swapped
+/// in place on reload.
+pub struct Translations {
+    dir: PathBuf,
+    bundles: RwLock<Arc<Bundles>>,
+}
+
+impl Translations {
+    pub fn load(dir: impl Into<PathBuf>) -> Result<Self, TranslationError> {
+        let dir = dir.into();
+        let bundles = load_bundles(&dir)?;
+
+        Ok(Self {
+            dir,
+            bundles: RwLock::new(Arc::new(bundles)),
+        })
+    }
+
+    /// Loads the directory again, keeping the current bundles if any locale fails.
+    pub fn reload(&self) -> Result<Vec<LanguageIdentifier>, TranslationError> {
+        let bundles = load_bundles(&self.dir)?;
+        let locales = bundles.iter().map(|(locale, _)| locale.clone()).collect();
+
+        *self.bundles.write().unwrap() = Arc::new(bundles);
+
+        Ok(locales)
+    }
+
+    fn bundles(&self) -> Arc<Bundles> {
+        self.bundles.read().unwrap().clone()
+    }
+
+    pub fn locales(&self) -> Vec<LanguageIdentifier> {
+        self.bundles()
+            .iter()
+            .map(|(locale, _)| locale.clone())
+            .collect()
+    }
+
+    pub fn default_bundle(&self) -> Arc<TranslationType> {
+        self.get(DEFAULT_LOCALE).unwrap()
+    }
+
+    /// Finds the bundle for `locale`, matching on the language alone when
+    /// there's no exact match (`en` or `en-GB` select `en-US`).
+    pub fn get(&self, locale: &str) -> Option<Arc<TranslationType>> {
+        let locale: LanguageIdentifier = locale.parse().ok()?;
+        let bundles = self.bundles();
+
+        bundles
+            .iter()
+            .find(|(value, _)| *value == locale)
+            .or_else(|| {
+                bundles
+                    .iter()
+                    .find(|(value, _)| value.language == locale.language)
+            })
+            .map(|(_, bundle)| bundle.clone())
+    }
+
+    pub fn negotiate<'a>(
+        &self,
+        preferred: impl IntoIterator<Item = Option<&'a str>>,
+    ) -> Arc<TranslationType> {
+        preferred
+            .into_iter()
+            .flatten()
+            .find_map(|locale| self.get(locale))
+            .unwrap_or_else(|| self.default_bundle())
+    }
+}
+
+/// Builds the bundle for a single `.ftl` file, which must define every message
+/// in the catalogue.
+fn load_bundle(path: &Path) -> Result<(LanguageIdentifier, TranslationType), TranslationError> {
+    let locale: LanguageIdentifier = path
+        .file_stem()
+        .and_then(|stem| stem.to_str())
+        .and_then(|stem| stem.parse().ok())
+        .ok_or_else(|| TranslationError::Parse(path.to_owned(), "invalid locale".to_string()))?;
+
+    let source =
+        fs::read_to_string(path).map_err(|err| TranslationError::Io(path.to_owned(), err))?;
+    let resource = FluentResource::try_new(source)
+        .map_err(|(_, errors)| TranslationError::Parse(path.to_owned(), format!("{:?}", errors)))?;
+
+    let mut lang_bundle: TranslationType = FluentBundle::new_concurrent(vec![locale.clone()]);
 
     lang_bundle
+        .add_resource(resource)
+        .map_err(|errors| TranslationError::Parse(path.to_owned(), format!("{:?}", errors)))?;
+
+    let missing: Vec<_> = Text::IDS
+        .iter()
+        .copied()
+        .filter(|id| {
+            lang_bundle
+                .get_message(id)
+                .and_then(|message| message.value())
+                .is_none()
+        })
+        .collect();
+
+    if !missing.is_empty() {
+        return Err(TranslationError::MissingMessages(
+            locale.to_string(),
+            missing,
+        ));
+    }
+
+    Ok((locale, lang_bundle))
+}
+
+fn load_bundles(dir: &Path) -> Result<Bundles, TranslationError> {
+    let mut paths = fs::read_dir(dir)
+        .map_err(|err| TranslationError::Io(dir.to_owned(), err))?
+        .filter_map(|entry| entry.ok().map(|entry| entry.path()))
+        .filter(|path| path.extension().is_some_and(|extension| extension == "ftl"))
+        .collect::<Vec<_>>();
+    paths.sort();
+
+    let mut bundles = vec![];
+
+    for path in paths {
+        let (locale, lang_bundle) = load_bundle(&path)?;
+        bundles.push((locale, Arc::new(lang_bundle)));
+    }
+
+    if !bundles.iter().any(|(locale, _)| *locale == DEFAULT_LOCALE) {
+        return Err(TranslationError::MissingDefault(dir.to_owned()));
+    }
+
+    Ok(bundles)
+}
+
+/// Selects the user's bundle from their chosen locale, then the Telegram
+/// `language_code`, remembering the latter for background notifications.
+pub async fn user_bundle(
+    translations: &Translations,
+    conn: &mut Connection<'_>,
+    user_id: i64,
+    language_code: Option<&str>,
+) -> Arc<TranslationType> {
+    let user_settings = get_user_settings(conn, user_id).await.ok();
+
+    if let Some(language_code) = language_code {
+        let stored = user_settings
+            .as_ref()
+            .and_then(|value| value.language_code.as_deref());
+
+        if stored != Some(language_code) {
+            upsert_user_language_code(conn, user_id, language_code)
+                .await
+                .unwrap();
+        }
+    }
+
+    let locale = user_settings
+        .as_ref()
+        .and_then(|value| value.locale.as_deref());
+    let stored_language_code = user_settings
+        .as_ref()
+        .and_then(|value| value.language_code.as_deref());
+
+    translations.negotiate([locale, language_code, stored_language_code])
 }
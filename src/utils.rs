@@ -1,21 +1,151 @@
-use std::env;
+use std::{env, path::PathBuf, str::FromStr, time::Duration};
 
 use teloxide::{adaptors::DefaultParseMode, Bot as TeloxideBot};
 
+use crate::{access::AccessMatrix, sharing::SharingPolicy};
+
 pub type Bot = DefaultParseMode<TeloxideBot>;
 
-#[derive(Clone, Copy)]
+/// Parses the environment variable `name`, panicking on a malformed value.
+pub fn parse_env<T: FromStr>(name: &str) -> Option<T> {
+    env::var(name).ok().map(|value| {
+        value
+            .trim()
+            .parse()
+            .unwrap_or_else(|_| panic!("Unable to parse environment variable `{}`.", name))
+    })
+}
+
+#[derive(Clone)]
 pub struct Config {
-    pub chat_id: i64,
+    pub access: AccessMatrix,
+    pub admins: Vec<i64>,
+    pub blogs: Vec<String>,
+    pub verification_ttl: Duration,
+    pub verification_attempts: i32,
+    pub verification_cooldown: Duration,
+    pub invite_link_ttl: Duration,
+    pub reminder_days: Vec<i32>,
+    pub grace_period: Duration,
+    pub suspension_period: Duration,
+    pub relink_cooldown: Duration,
+    pub sharing: SharingPolicy,
+    pub decline_notice_cooldown: Duration,
+    pub join_retry_interval: Duration,
+    pub join_request_max_age: Duration,
+    pub translations_dir: PathBuf,
 }
 
 impl Config {
     pub fn build() -> Self {
-        let chat_id: i64 = env::var("CHAT_ID")
-            .expect("Environment variable `CHAT_ID` is not provided.")
-            .parse()
-            .expect("Unable to convert enviromnent variable `CHAT_ID` to i64");
+        let access = match env::var("ACCESS_CONFIG") {
+            Ok(path) => AccessMatrix::load(&path),
+            Err(_) => {
+                let chat_id: i64 = env::var("CHAT_ID")
+                    .expect(
+                        "Neither `ACCESS_CONFIG` nor `CHAT_ID` environment variable is provided.",
+                    )
+                    .parse()
+                    .expect("Unable to convert enviromnent variable `CHAT_ID` to i64");
+
+                AccessMatrix::single_chat(chat_id)
+            }
+        };
+
+        if access.chats().is_empty() {
+            panic!("Access config must grant at least one chat.");
+        }
+
+        let admins: Vec<i64> = env::var("ADMIN_IDS")
+            .unwrap_or_default()
+            .split(',')
+            .map(|id| id.trim())
+            .filter(|id| !id.is_empty())
+            .map(|id| {
+                id.parse()
+                    .expect("Unable to convert environment variable `ADMIN_IDS` to a list of i64")
+            })
+            .collect();
+
+        let blogs: Vec<String> = env::var("BOOSTY_BLOGS")
+            .expect("Environment variable `BOOSTY_BLOGS` is not provided.")
+            .split(',')
+            .map(|blog| blog.trim().to_string())
+            .filter(|blog| !blog.is_empty())
+            .collect();
+
+        if blogs.is_empty() {
+            panic!("Environment variable `BOOSTY_BLOGS` must contain at least one blog slug.");
+        }
+
+        let verification_ttl =
+            Duration::from_secs(parse_env::<u64>("VERIFICATION_TTL_MINUTES").unwrap_or(15) * 60);
+
+        let verification_attempts = parse_env("VERIFICATION_MAX_ATTEMPTS").unwrap_or(5);
+
+        let verification_cooldown =
+            Duration::from_secs(parse_env("VERIFICATION_COOLDOWN_SECONDS").unwrap_or(60));
+
+        let invite_link_ttl =
+            Duration::from_secs(parse_env::<u64>("INVITE_LINK_TTL_HOURS").unwrap_or(24) * 60 * 60);
+
+        let mut reminder_days: Vec<i32> = env::var("EXPIRY_REMINDER_DAYS")
+            .unwrap_or_else(|_| "7,3,1".to_string())
+            .split(',')
+            .map(|days| days.trim())
+            .filter(|days| !days.is_empty())
+            .map(|days| {
+                days.parse().expect(
+                    "Unable to convert environment variable `EXPIRY_REMINDER_DAYS` to a list of i32",
+                )
+            })
+            .collect();
+        reminder_days.sort_unstable();
+        reminder_days.dedup();
+
+        let grace_period =
+            Duration::from_secs(parse_env::<u64>("GRACE_PERIOD_DAYS").unwrap_or(3) * 24 * 60 * 60);
+
+        let suspension_period = Duration::from_secs(
+            parse_env::<u64>("SUSPENSION_PERIOD_DAYS").unwrap_or(30) * 24 * 60 * 60,
+        );
+
+        let relink_cooldown = Duration::from_secs(
+            parse_env::<u64>("RELINK_COOLDOWN_DAYS").unwrap_or(30) * 24 * 60 * 60,
+        );
+
+        let decline_notice_cooldown = Duration::from_secs(
+            parse_env::<u64>("DECLINE_NOTICE_COOLDOWN_MINUTES").unwrap_or(60) * 60,
+        );
+
+        let join_retry_interval =
+            Duration::from_secs(parse_env("JOIN_RETRY_INTERVAL_SECONDS").unwrap_or(60));
+
+        let join_request_max_age = Duration::from_secs(
+            parse_env::<u64>("JOIN_REQUEST_MAX_AGE_HOURS").unwrap_or(24) * 60 * 60,
+        );
+
+        let translations_dir = PathBuf::from(
+            env::var("TRANSLATIONS_DIR").unwrap_or_else(|_| "assets/translations".to_string()),
+        );
 
-        Self { chat_id }
+        Self {
+            access,
+            admins,
+            blogs,
+            verification_ttl,
+            verification_attempts,
+            verification_cooldown,
+            invite_link_ttl,
+            reminder_days,
+            grace_period,
+            suspension_period,
+            relink_cooldown,
+            sharing: SharingPolicy::from_env(),
+            decline_notice_cooldown,
+            join_retry_interval,
+            join_request_max_age,
+            translations_dir,
+        }
     }
 }